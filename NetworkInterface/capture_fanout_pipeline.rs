@@ -0,0 +1,418 @@
+# 🔹 Big Picture (Why this program exists)
+
+* The sniffer in `capture_parse_tcp_ip_ethernet.rs` does **everything on one thread**: `rx.next()` → decode → `println!` → `rx.next()` again.
+* Under load, decoding and printing are slower than the NIC, so the socket buffer fills up and the **kernel drops frames** before we ever see them.
+* Fix = turn capture into a **pipeline**, like a factory line:
+
+  1. **Receive threads** – only pull frames off the socket (nothing else!).
+  2. **Decode workers** – turn raw bytes into readable lines.
+  3. **Output stage** – prints lines **in the order they were captured**.
+
+* Linux can split one interface between several sockets with **packet fanout** (`PACKET_FANOUT`). pnet exposes it as `Config::linux_fanout`.
+* We also count drops at every stage, so you can **measure** the gain instead of guessing.
+
+---
+
+# 🔹 Project Setup
+
+```sh
+cargo new sniffer_pipeline --bin
+cd sniffer_pipeline
+```
+
+`Cargo.toml`:
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+crossbeam-channel = "0.5"
+```
+
+👉 `crossbeam-channel` gives us a **bounded, array-based channel**. Its fast path is lock-free, so the receive thread never waits on a mutex.
+
+👉 The interface is resolved once in `main` with `src/select.rs` (from `interface_networks.rs`); every rx thread then opens its socket on that same `NetworkInterface`, so they can't end up on different interfaces.
+
+---
+
+# 🔹 Full Code (`src/main.rs`)
+
+```rust
+mod select;
+
+use clap::Parser;
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+use pnet::datalink::linux::{FanoutOption, FanoutType};
+use pnet::datalink::{self, Channel, Config, NetworkInterface};
+use pnet::packet::ethernet::{EtherTypes, EthernetPacket};
+use pnet::packet::ip::IpNextHeaderProtocols;
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::tcp::TcpPacket;
+use pnet::packet::udp::UdpPacket;
+use pnet::packet::Packet;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Parser)]
+struct Cli {
+    /// Interface to capture on (e.g. eth0, lo)
+    #[arg(long)]
+    iface: String,
+
+    /// Enable promiscuous mode
+    #[arg(long)]
+    promisc: bool,
+
+    /// Number of receive threads (one fanout socket each)
+    #[arg(long, default_value_t = 4)]
+    rx_threads: usize,
+
+    /// Number of decode worker threads
+    #[arg(long, default_value_t = 4)]
+    workers: usize,
+
+    /// Capacity of the rx -> worker queue (frames)
+    #[arg(long, default_value_t = 65536)]
+    queue: usize,
+
+    /// Fanout mode: hash, lb, cpu
+    #[arg(long, default_value = "hash")]
+    fanout: String,
+
+    /// Stop after this many seconds (0 = run forever)
+    #[arg(long, default_value_t = 0)]
+    seconds: u64,
+
+    /// Only print statistics, not every packet
+    #[arg(long)]
+    quiet: bool,
+}
+
+/// One raw frame handed from a receive thread to a decode worker.
+struct Job {
+    rx_id: usize,
+    seq: u64,
+    data: Vec<u8>,
+}
+
+/// One decoded line handed from a worker to the output stage.
+struct Decoded {
+    rx_id: usize,
+    seq: u64,
+    line: String,
+}
+
+/// Counters shared by every stage.
+#[derive(Default)]
+struct Stats {
+    received: AtomicU64,
+    queue_drops: AtomicU64,
+    rx_errors: AtomicU64,
+    decoded: AtomicU64,
+    printed: AtomicU64,
+}
+
+fn fanout_type(name: &str) -> FanoutType {
+    match name {
+        "lb" => FanoutType::LB,
+        "cpu" => FanoutType::CPU,
+        _ => FanoutType::HASH,
+    }
+}
+
+fn decode(frame: &[u8]) -> String {
+    let eth = match EthernetPacket::new(frame) {
+        Some(eth) => eth,
+        None => return "Malformed ethernet packet".to_string(),
+    };
+    match eth.get_ethertype() {
+        EtherTypes::Ipv4 => match Ipv4Packet::new(eth.payload()) {
+            Some(ipv4) => {
+                let (src, dst) = (ipv4.get_source(), ipv4.get_destination());
+                match ipv4.get_next_level_protocol() {
+                    IpNextHeaderProtocols::Tcp => match TcpPacket::new(ipv4.payload()) {
+                        Some(tcp) => format!(
+                            "IPv4 TCP {}:{} -> {}:{}",
+                            src, tcp.get_source(), dst, tcp.get_destination()
+                        ),
+                        None => format!("IPv4 TCP {} -> {} (truncated)", src, dst),
+                    },
+                    IpNextHeaderProtocols::Udp => match UdpPacket::new(ipv4.payload()) {
+                        Some(udp) => format!(
+                            "IPv4 UDP {}:{} -> {}:{}",
+                            src, udp.get_source(), dst, udp.get_destination()
+                        ),
+                        None => format!("IPv4 UDP {} -> {} (truncated)", src, dst),
+                    },
+                    other => format!("IPv4 {} -> {} proto {}", src, dst, other),
+                }
+            }
+            None => "Malformed IPv4 packet".to_string(),
+        },
+        EtherTypes::Ipv6 => "IPv6 packet".to_string(),
+        other => format!("ethertype {}", other),
+    }
+}
+
+fn rx_loop(
+    rx_id: usize,
+    iface: NetworkInterface,
+    config: Config,
+    jobs: Sender<Job>,
+    stats: Arc<Stats>,
+    running: Arc<AtomicBool>,
+) {
+    let mut rx = match datalink::channel(&iface, config) {
+        Ok(Channel::Ethernet(_tx, rx)) => rx,
+        Ok(_) => {
+            eprintln!("rx{}: unsupported channel type on this platform", rx_id);
+            return;
+        }
+        Err(e) => {
+            eprintln!("rx{}: failed to open datalink channel: {}", rx_id, e);
+            return;
+        }
+    };
+
+    // Only this thread sends with this rx_id, so `seq` has no gaps:
+    // it is only bumped after a frame really entered the queue.
+    let mut seq = 0u64;
+    while running.load(Ordering::Relaxed) {
+        match rx.next() {
+            Ok(frame) => {
+                stats.received.fetch_add(1, Ordering::Relaxed);
+                let job = Job { rx_id, seq, data: frame.to_vec() };
+                match jobs.try_send(job) {
+                    Ok(()) => seq += 1,
+                    Err(TrySendError::Full(_)) => {
+                        // Never block the receive thread: count and move on.
+                        stats.queue_drops.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Err(TrySendError::Disconnected(_)) => break,
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+            Err(_) => {
+                stats.rx_errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+fn worker_loop(jobs: Receiver<Job>, out: Sender<Decoded>, stats: Arc<Stats>) {
+    for job in jobs {
+        let line = decode(&job.data);
+        stats.decoded.fetch_add(1, Ordering::Relaxed);
+        if out.send(Decoded { rx_id: job.rx_id, seq: job.seq, line }).is_err() {
+            break;
+        }
+    }
+}
+
+fn output_loop(decoded: Receiver<Decoded>, rx_threads: usize, quiet: bool, stats: Arc<Stats>) {
+    // Workers finish out of order, so keep a small reorder buffer per
+    // receive thread and only print when the next expected seq arrives.
+    let mut next_seq = vec![0u64; rx_threads];
+    let mut pending: Vec<BTreeMap<u64, String>> = vec![BTreeMap::new(); rx_threads];
+
+    for d in decoded {
+        pending[d.rx_id].insert(d.seq, d.line);
+        while let Some(line) = pending[d.rx_id].remove(&next_seq[d.rx_id]) {
+            if !quiet {
+                println!("[rx{}] {}", d.rx_id, line);
+            }
+            next_seq[d.rx_id] += 1;
+            stats.printed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Kernel/driver drop counter for the interface (not per socket).
+fn interface_rx_dropped(iface: &str) -> u64 {
+    std::fs::read_to_string(format!("/sys/class/net/{}/statistics/rx_dropped", iface))
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+fn print_stats(stats: &Stats, iface: &str, base_dropped: u64, started: Instant) {
+    let secs = started.elapsed().as_secs_f64().max(0.001);
+    let received = stats.received.load(Ordering::Relaxed);
+    eprintln!(
+        "stats: received={} ({:.0} pps) queue_drops={} rx_errors={} decoded={} printed={} iface_rx_dropped={}",
+        received,
+        received as f64 / secs,
+        stats.queue_drops.load(Ordering::Relaxed),
+        stats.rx_errors.load(Ordering::Relaxed),
+        stats.decoded.load(Ordering::Relaxed),
+        stats.printed.load(Ordering::Relaxed),
+        interface_rx_dropped(iface).saturating_sub(base_dropped),
+    );
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let iface = select::interface_or_exit(&cli.iface);
+
+    let mut config = Config::default();
+    config.read_timeout = Some(Duration::from_millis(200));
+    config.promiscuous = cli.promisc;
+    config.read_buffer_size = 1 << 20;
+    // Every socket joins the same fanout group; the kernel spreads frames
+    // between them. HASH keeps one flow on one socket (so flows stay ordered).
+    config.linux_fanout = Some(FanoutOption {
+        group_id: (std::process::id() & 0xffff) as u16,
+        fanout_type: fanout_type(&cli.fanout),
+        defrag: true,
+        rollover: false,
+    });
+
+    let stats = Arc::new(Stats::default());
+    let running = Arc::new(AtomicBool::new(true));
+    let (jobs_tx, jobs_rx) = bounded::<Job>(cli.queue);
+    let (out_tx, out_rx) = bounded::<Decoded>(cli.queue);
+
+    println!(
+        "Listening on {} with {} rx threads, {} workers, fanout={}",
+        iface.name, cli.rx_threads, cli.workers, cli.fanout
+    );
+
+    let mut rx_handles = Vec::new();
+    for rx_id in 0..cli.rx_threads {
+        let (iface, config) = (iface.clone(), config.clone());
+        let (jobs, stats, running) = (jobs_tx.clone(), stats.clone(), running.clone());
+        rx_handles.push(thread::spawn(move || rx_loop(rx_id, iface, config, jobs, stats, running)));
+    }
+    drop(jobs_tx);
+
+    let mut worker_handles = Vec::new();
+    for _ in 0..cli.workers {
+        let (jobs, out, stats) = (jobs_rx.clone(), out_tx.clone(), stats.clone());
+        worker_handles.push(thread::spawn(move || worker_loop(jobs, out, stats)));
+    }
+    drop(out_tx);
+
+    let output = {
+        let (stats, rx_threads, quiet) = (stats.clone(), cli.rx_threads, cli.quiet);
+        thread::spawn(move || output_loop(out_rx, rx_threads, quiet, stats))
+    };
+
+    let started = Instant::now();
+    let base_dropped = interface_rx_dropped(&iface.name);
+    while cli.seconds == 0 || started.elapsed() < Duration::from_secs(cli.seconds) {
+        thread::sleep(Duration::from_secs(1));
+        print_stats(&stats, &iface.name, base_dropped, started);
+    }
+
+    // Shut down front to back: rx threads stop, the job channel closes,
+    // workers drain it, then the output stage drains and exits.
+    running.store(false, Ordering::Relaxed);
+    for h in rx_handles.into_iter().chain(worker_handles) {
+        let _ = h.join();
+    }
+    let _ = output.join();
+    print_stats(&stats, &iface.name, base_dropped, started);
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+config.linux_fanout = Some(FanoutOption { group_id, fanout_type, defrag: true, rollover: false });
+```
+
+👉 This is the whole trick. Each receive thread opens **its own** `datalink::channel` with the same `group_id`, and the kernel splits the traffic between those sockets.
+
+* `FanoutType::HASH` → all packets of one flow go to the same socket (keeps a TCP conversation in order).
+* `FanoutType::LB` → round-robin, best spread but flows get mixed between threads.
+* `FanoutType::CPU` → socket chosen by the CPU that received the packet.
+* `defrag: true` → IP fragments are reassembled first so they hash to the same socket.
+
+---
+
+```rust
+match jobs.try_send(job) {
+    Ok(()) => seq += 1,
+    Err(TrySendError::Full(_)) => { stats.queue_drops.fetch_add(1, Ordering::Relaxed); }
+```
+
+👉 The receive thread **never waits**. If workers are behind, we drop in user space and count it (`queue_drops`) — much better than the kernel dropping silently.
+
+👉 `seq` only grows when a frame really entered the queue, so the output stage never waits for a number that will never come.
+
+---
+
+```rust
+pending[d.rx_id].insert(d.seq, d.line);
+while let Some(line) = pending[d.rx_id].remove(&next_seq[d.rx_id]) { ... }
+```
+
+👉 Workers finish in random order. The output stage keeps a tiny **reorder buffer** (`BTreeMap`) per receive thread and prints only when the next expected number shows up.
+
+👉 With `HASH` fanout this means every flow is printed **in capture order**.
+
+---
+
+```rust
+fn interface_rx_dropped(iface: &str) -> u64
+```
+
+👉 pnet does not expose the socket's `PACKET_STATISTICS`, so we read the interface's `rx_dropped` counter from `/sys`. Together with `queue_drops` this shows where frames are lost:
+
+* `queue_drops` grows → add more `--workers`.
+* `iface_rx_dropped` grows → add more `--rx-threads` or a bigger `read_buffer_size`.
+
+---
+
+```rust
+running.store(false, Ordering::Relaxed);
+```
+
+👉 Clean shutdown in pipeline order: receivers stop → channel closes → workers drain → output drains → final stats.
+
+---
+
+# 🔹 Measuring the Gain on Loopback
+
+Terminal 1 – generate traffic (UDP flood on loopback):
+
+```sh
+iperf3 -s &
+iperf3 -c 127.0.0.1 -u -b 0 -l 64 -t 30 -P 4
+```
+
+Terminal 2 – old behaviour (1 receive thread, 1 worker) vs. the pipeline:
+
+```sh
+sudo ./target/release/sniffer_pipeline --iface lo --rx-threads 1 --workers 1 --seconds 20 --quiet
+sudo ./target/release/sniffer_pipeline --iface lo --rx-threads 4 --workers 4 --seconds 20 --quiet
+```
+
+👉 Always use `--quiet` for benchmarks; printing to a terminal is slower than any decoder.
+
+Example output:
+
+```
+Listening on lo with 4 rx threads, 4 workers, fanout=hash
+stats: received=412803 (412611 pps) queue_drops=0 rx_errors=0 decoded=412803 printed=412803 iface_rx_dropped=0
+stats: received=830117 (414902 pps) queue_drops=0 rx_errors=0 decoded=830117 printed=830117 iface_rx_dropped=0
+...
+```
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: one thread can't keep up with a busy link, and lost frames are invisible.
+* **How**:
+
+  1. Several receive threads share the interface with **PACKET_FANOUT**.
+  2. They hand frames to decode workers through a **bounded lock-free queue**.
+  3. An output stage puts lines back **in order**.
+  4. Drop counters at every stage tell you which part to scale.
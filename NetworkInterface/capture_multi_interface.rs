@@ -0,0 +1,301 @@
+# 🔹 Big Picture (Why this program exists)
+
+* The basic sniffer listens on **one** interface (`--iface eth0`).
+* Real debugging often needs **several at once**:
+
+  * both sides of a **bridge** (`br0` and its ports),
+  * the host side and the container side of a **veth pair**,
+  * "just show me everything" → `any`.
+
+* Idea:
+
+  1. Let `--iface` be **repeated** (`--iface veth0 --iface br0`), take a glob that matches several (`--iface 'veth*'`), or take `any`.
+  2. Open **one `datalink::channel` per interface**, each in its own thread.
+  3. **Merge** all frames into one output stream, each line tagged with the interface name.
+
+---
+
+# 🔹 Project Setup
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+```
+
+👉 Only the standard library's `mpsc` channel is needed for merging — no extra crates.
+
+👉 `src/select.rs` comes from `interface_networks.rs`. A single-interface sniffer treats a glob matching several interfaces as an error; here that is exactly what we want, so every match is opened.
+
+---
+
+# 🔹 Full Code (`src/main.rs`)
+
+```rust
+mod select;
+
+use clap::Parser;
+use pnet::datalink::{self, Channel, Config, NetworkInterface};
+use pnet::packet::ethernet::{EtherTypes, EthernetPacket};
+use pnet::packet::ip::IpNextHeaderProtocols;
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::tcp::TcpPacket;
+use pnet::packet::Packet;
+use select::SelectError;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Parser)]
+struct Cli {
+    /// List available interfaces
+    #[arg(long)]
+    list: bool,
+
+    /// Interface (name, index, IP, glob, `default` ...) to capture on; repeat it for more, or use `any`
+    #[arg(long = "iface")]
+    ifaces: Vec<String>,
+
+    /// Enable promiscuous mode on every opened interface
+    #[arg(long)]
+    promisc: bool,
+}
+
+/// A frame plus where and when it was seen.
+struct TaggedFrame {
+    iface: String,
+    ts: Duration,
+    data: Vec<u8>,
+}
+
+fn print_interfaces() {
+    for iface in datalink::interfaces() {
+        println!("{}  mac:{:?} ips:{:?}", iface.name, iface.mac, iface.ips);
+    }
+}
+
+/// Turn the `--iface` values into real interfaces, each resolved by select.rs.
+fn select_interfaces(queries: &[String]) -> Result<Vec<NetworkInterface>, String> {
+    let all = datalink::interfaces();
+
+    if queries.iter().any(|q| q == "any") {
+        // `any` = every interface that is up
+        return Ok(all.into_iter().filter(|i| i.is_up()).collect());
+    }
+
+    let mut selected: Vec<NetworkInterface> = Vec::new();
+    for query in queries {
+        let found = match select::find_interface_by_name(query) {
+            Ok(iface) => vec![iface],
+            // `veth*` matching several interfaces: capture on all of them.
+            Err(SelectError::Ambiguous { matches, .. }) => {
+                all.iter().filter(|i| matches.contains(&i.name)).cloned().collect()
+            }
+            Err(e) => return Err(e.to_string()),
+        };
+        for iface in found {
+            if !selected.iter().any(|s| s.name == iface.name) {
+                selected.push(iface);
+            }
+        }
+    }
+    Ok(selected)
+}
+
+/// One capture thread: read frames from `iface` and push them into the merged stream.
+fn capture(iface: NetworkInterface, config: Config, out: mpsc::Sender<TaggedFrame>) {
+    let mut rx = match datalink::channel(&iface, config) {
+        Ok(Channel::Ethernet(_tx, rx)) => rx,
+        Ok(_) => {
+            eprintln!("{}: unsupported channel type on this platform", iface.name);
+            return;
+        }
+        Err(e) => {
+            eprintln!("{}: failed to open datalink channel: {}", iface.name, e);
+            return;
+        }
+    };
+    println!("Listening on {}", iface.name);
+
+    loop {
+        match rx.next() {
+            Ok(frame) => {
+                let ts = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+                let tagged = TaggedFrame { iface: iface.name.clone(), ts, data: frame.to_vec() };
+                if out.send(tagged).is_err() {
+                    return; // printer is gone, stop capturing
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+            Err(e) => eprintln!("{}: receive error: {:?}", iface.name, e),
+        }
+    }
+}
+
+/// Same decode as the single-interface sniffer, but returns a line instead of printing.
+fn decode(frame: &[u8]) -> String {
+    let eth = match EthernetPacket::new(frame) {
+        Some(eth) => eth,
+        None => return "Malformed ethernet packet".to_string(),
+    };
+    match eth.get_ethertype() {
+        EtherTypes::Ipv4 => match Ipv4Packet::new(eth.payload()) {
+            Some(ipv4) => {
+                let mut line = format!("IPv4: {} -> {}", ipv4.get_source(), ipv4.get_destination());
+                if ipv4.get_next_level_protocol() == IpNextHeaderProtocols::Tcp {
+                    if let Some(tcp) = TcpPacket::new(ipv4.payload()) {
+                        line += &format!("  TCP: {} -> {}", tcp.get_source(), tcp.get_destination());
+                    }
+                }
+                line
+            }
+            None => "Malformed IPv4 packet".to_string(),
+        },
+        EtherTypes::Ipv6 => "IPv6 packet".to_string(),
+        other => format!("ethertype {}", other),
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    if cli.list {
+        print_interfaces();
+        return;
+    }
+
+    if cli.ifaces.is_empty() {
+        eprintln!("Please specify --iface <name> (repeatable), --iface any, or use --list");
+        return;
+    }
+
+    let ifaces = match select_interfaces(&cli.ifaces) {
+        Ok(ifaces) if !ifaces.is_empty() => ifaces,
+        Ok(_) => {
+            eprintln!("No interfaces are up");
+            return;
+        }
+        Err(msg) => {
+            eprintln!("{}", msg);
+            std::process::exit(1);
+        }
+    };
+
+    let mut config = Config::default();
+    config.read_timeout = Some(Duration::from_secs(1));
+    config.promiscuous = cli.promisc;
+
+    // Width of the name column, so lines from different interfaces line up. Taken from the
+    // resolved names: `any`, `eth*` or `default` on the command line say nothing about them.
+    let width = ifaces.iter().map(|i| i.name.len()).max().unwrap_or(4).max(4);
+
+    // All capture threads share one sender; the main thread is the single reader.
+    let (tx, rx) = mpsc::channel::<TaggedFrame>();
+    for iface in ifaces {
+        let (tx, config) = (tx.clone(), config.clone());
+        thread::spawn(move || capture(iface, config, tx));
+    }
+    drop(tx); // so `rx` ends when every capture thread has stopped
+
+    for frame in rx {
+        println!(
+            "{}.{:06} {:<width$} {}",
+            frame.ts.as_secs(),
+            frame.ts.subsec_micros(),
+            frame.iface,
+            decode(&frame.data),
+            width = width
+        );
+    }
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+#[arg(long = "iface")]
+ifaces: Vec<String>,
+```
+
+👉 A `Vec` field makes clap accept the flag **many times**: `--iface veth0 --iface br0` → `["veth0", "br0"]`.
+
+---
+
+```rust
+if names.iter().any(|n| n == "any") {
+    return Ok(all.into_iter().filter(|i| i.is_up()).collect());
+}
+```
+
+👉 `any` expands to every interface that is **up**. We open them one by one (pnet has no "any" pseudo-device like tcpdump), so each line still knows its real interface.
+
+---
+
+```rust
+Err(SelectError::Ambiguous { matches, .. }) => all.iter().filter(|i| matches.contains(&i.name)).cloned().collect(),
+```
+
+👉 Every other value goes through `find_interface_by_name`, so `--iface 2`, `--iface default` or `--iface 10.0.0.0/24` work here too. The one difference: a glob with several matches isn't an error, it selects them all. The same interface given twice (`--iface eth0 --iface 2`) is opened once.
+
+---
+
+```rust
+let (tx, rx) = mpsc::channel::<TaggedFrame>();
+for iface in ifaces {
+    thread::spawn(move || capture(iface, config, tx));
+}
+```
+
+👉 One thread per interface, because `rx.next()` **blocks** on its own socket. All threads send into **one** channel → that is the merge.
+
+👉 `drop(tx)` matters: the `for frame in rx` loop only ends when **every** sender is gone.
+
+---
+
+```rust
+println!("{}.{:06} {:<width$} {}", ..., frame.iface, decode(&frame.data));
+```
+
+👉 Each line is tagged with a timestamp (taken right after `rx.next()`) and the interface name, so you can follow the **same packet** crossing a bridge or veth pair.
+
+👉 `width` comes from the interfaces that were actually opened, before they move into their threads — with `--iface any` the longest name might be `vethb3f21c9`, not `any`.
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: bridges, veth pairs and containers mean one packet crosses several interfaces.
+* **What**:
+
+  1. `--iface` can be repeated, takes anything `select.rs` understands (a glob may match several), or `any` picks every up interface.
+  2. One capture thread + `datalink::channel` per interface.
+  3. A single `mpsc` channel merges everything into one stream.
+  4. Each line shows time + interface + decoded packet.
+
+---
+
+👉 Example Run:
+
+```sh
+# watch both ends of a veth pair
+sudo cargo run -- --iface veth-host --iface veth-ns
+
+# every veth on the host
+sudo cargo run -- --iface 'veth*'
+
+# everything
+sudo cargo run -- --iface any
+```
+
+Output:
+
+```
+Listening on veth-host
+Listening on veth-ns
+1729241101.203311 veth-ns   IPv4: 10.0.0.2 -> 10.0.0.1  TCP: 41522 -> 80
+1729241101.203329 veth-host IPv4: 10.0.0.2 -> 10.0.0.1  TCP: 41522 -> 80
+1729241101.203611 veth-host IPv4: 10.0.0.1 -> 10.0.0.2  TCP: 80 -> 41522
+1729241101.203624 veth-ns   IPv4: 10.0.0.1 -> 10.0.0.2  TCP: 80 -> 41522
+```
@@ -112,12 +112,17 @@ fn main() {
 ```rust
     // find the interface
     let interfaces = datalink::interfaces();
-    let iface = interfaces.into_iter()
-        .find(|i| i.name == iface_name)
-        .expect("Interface not found");
+    let iface = match interfaces.into_iter().find(|i| i.name == iface_name) {
+        Some(i) => i,
+        None => {
+            eprintln!("Interface '{}' not found. Use --list to see names.", iface_name);
+            return;
+        }
+    };
 ```
 
 👉 Search for the chosen interface in all available ones.
+If it isn't there, say so and exit instead of panicking. (`select.rs` in `interface_networks.rs` shows a smarter lookup: by index, IP, MAC, glob, `default`, with "did you mean" suggestions.)
 
 ---
 
@@ -259,6 +264,8 @@ If it contains **IPv4**, then decode deeper.
   4. Decode into IP, then TCP.
   5. Print details like source/destination IPs and ports.
 
+* **Testing**: `frame_builder.rs` moves the decoding into `dissect.rs` and tests it with frames built by `FrameBuilder` — no live interface needed.
+
 ---
 
 👉 Example Run:
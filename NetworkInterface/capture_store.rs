@@ -0,0 +1,546 @@
+# 🔹 Big Picture (Why this program exists)
+
+* Long captures get **big**: a day on a busy link is many gigabytes of pcap.
+* The usual question is narrow: *"all packets between 14:02 and 14:05 involving host X on port Y"*. Answering it with `tcpdump -r` means reading **every byte** of every file.
+* This program is a small **capture store**:
+
+  1. `record` – capture packets and write them into **segmented** pcap files (a new file every N seconds / N MB), plus a **side index** per segment with (timestamp, 5-tuple, protocol, file offset) for every packet.
+  2. `query` – use the indexes to find matching packets and copy only those into a **new pcap**.
+
+* Index entries are tiny and fixed-size, and sorted by time, so a query **binary-searches** to the start time, skips whole segments outside the range, and only touches the pcap bytes of matching packets.
+
+---
+
+# 🔹 On-disk Layout
+
+```
+store/
+├── seg-000001.pcap   <-- normal pcap (opens in Wireshark)
+├── seg-000001.idx    <-- 64 bytes per packet, same order as the pcap
+├── seg-000002.pcap
+├── seg-000002.idx
+└── ...
+```
+
+One index entry (64 bytes, little-endian):
+
+| bytes | field |
+|---|---|
+| 0..8 | timestamp, microseconds since 1970 |
+| 8..16 | offset of the packet's record header in the `.pcap` |
+| 16..32 | source IP (IPv4 mapped into IPv6 `::ffff:a.b.c.d`) |
+| 32..48 | destination IP |
+| 48..50 / 50..52 | source / destination port |
+| 52 | IP protocol (6 = TCP, 17 = UDP, ...) |
+| 53 | IP version (0 = not IP) |
+| 54..58 | captured length |
+| 58..64 | reserved |
+
+---
+
+# 🔹 Project Setup
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+pcap-file = "2"
+```
+
+👉 A recorder usually runs unattended from a unit file on several machines. `--iface` goes through `src/select.rs` (from `interface_networks.rs`), so `--iface default` or `--iface 10.0.0.0/8` can go into that unit file instead of each machine's NIC name.
+
+---
+
+# 🔹 Full Code (`src/main.rs`)
+
+```rust
+mod select;
+
+use clap::{Parser, Subcommand};
+use pcap_file::pcap::{PcapHeader, PcapPacket, PcapWriter};
+use pcap_file::Endianness;
+use pnet::datalink::{self, Channel, Config};
+use pnet::packet::ethernet::{EtherTypes, EthernetPacket};
+use pnet::packet::ip::IpNextHeaderProtocols;
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::ipv6::Ipv6Packet;
+use pnet::packet::tcp::TcpPacket;
+use pnet::packet::udp::UdpPacket;
+use pnet::packet::Packet;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::net::{IpAddr, Ipv6Addr};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const ENTRY_SIZE: usize = 64;
+const PCAP_GLOBAL_HEADER: u64 = 24;
+const PCAP_RECORD_HEADER: u64 = 16;
+
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Capture into a segmented, indexed store
+    Record {
+        #[arg(long)]
+        iface: String,
+        #[arg(long)]
+        store: PathBuf,
+        #[arg(long)]
+        promisc: bool,
+        /// Start a new segment after this many seconds
+        #[arg(long, default_value_t = 300)]
+        segment_secs: u64,
+        /// ...or after this many megabytes
+        #[arg(long, default_value_t = 256)]
+        segment_mb: u64,
+    },
+    /// Extract matching packets into a new pcap
+    Query {
+        #[arg(long)]
+        store: PathBuf,
+        /// Start time, unix seconds (e.g. `date -d '14:02' +%s`)
+        #[arg(long)]
+        from: Option<f64>,
+        /// End time, unix seconds
+        #[arg(long)]
+        to: Option<f64>,
+        /// Packets to or from this host
+        #[arg(long)]
+        host: Option<IpAddr>,
+        /// Packets to or from this port
+        #[arg(long)]
+        port: Option<u16>,
+        /// IP protocol number (6 = TCP, 17 = UDP)
+        #[arg(long)]
+        proto: Option<u8>,
+        /// Output pcap
+        #[arg(long)]
+        write: PathBuf,
+    },
+}
+
+/// One index entry (see the table above).
+#[derive(Clone, Copy)]
+struct IndexEntry {
+    ts_us: u64,
+    offset: u64,
+    src: Ipv6Addr,
+    dst: Ipv6Addr,
+    sport: u16,
+    dport: u16,
+    proto: u8,
+    ip_version: u8,
+    caplen: u32,
+}
+
+impl IndexEntry {
+    fn to_bytes(&self) -> [u8; ENTRY_SIZE] {
+        let mut b = [0u8; ENTRY_SIZE];
+        b[0..8].copy_from_slice(&self.ts_us.to_le_bytes());
+        b[8..16].copy_from_slice(&self.offset.to_le_bytes());
+        b[16..32].copy_from_slice(&self.src.octets());
+        b[32..48].copy_from_slice(&self.dst.octets());
+        b[48..50].copy_from_slice(&self.sport.to_le_bytes());
+        b[50..52].copy_from_slice(&self.dport.to_le_bytes());
+        b[52] = self.proto;
+        b[53] = self.ip_version;
+        b[54..58].copy_from_slice(&self.caplen.to_le_bytes());
+        b
+    }
+
+    fn from_bytes(b: &[u8]) -> IndexEntry {
+        let u16_at = |i: usize| u16::from_le_bytes([b[i], b[i + 1]]);
+        let ip_at = |i: usize| Ipv6Addr::from(<[u8; 16]>::try_from(&b[i..i + 16]).unwrap());
+        IndexEntry {
+            ts_us: u64::from_le_bytes(b[0..8].try_into().unwrap()),
+            offset: u64::from_le_bytes(b[8..16].try_into().unwrap()),
+            src: ip_at(16),
+            dst: ip_at(32),
+            sport: u16_at(48),
+            dport: u16_at(50),
+            proto: b[52],
+            ip_version: b[53],
+            caplen: u32::from_le_bytes(b[54..58].try_into().unwrap()),
+        }
+    }
+}
+
+/// Store every address as IPv6 so the index has one fixed layout.
+fn to_v6(ip: IpAddr) -> Ipv6Addr {
+    match ip {
+        IpAddr::V4(v4) => v4.to_ipv6_mapped(),
+        IpAddr::V6(v6) => v6,
+    }
+}
+
+/// Fill in the 5-tuple part of an index entry from an Ethernet frame.
+fn classify(frame: &[u8], e: &mut IndexEntry) {
+    let eth = match EthernetPacket::new(frame) {
+        Some(eth) => eth,
+        None => return,
+    };
+    let (proto, l4) = match eth.get_ethertype() {
+        EtherTypes::Ipv4 => match Ipv4Packet::new(eth.payload()) {
+            Some(ip) => {
+                e.src = to_v6(ip.get_source().into());
+                e.dst = to_v6(ip.get_destination().into());
+                e.ip_version = 4;
+                (ip.get_next_level_protocol(), ip.payload().to_vec())
+            }
+            None => return,
+        },
+        EtherTypes::Ipv6 => match Ipv6Packet::new(eth.payload()) {
+            Some(ip) => {
+                e.src = ip.get_source();
+                e.dst = ip.get_destination();
+                e.ip_version = 6;
+                (ip.get_next_header(), ip.payload().to_vec())
+            }
+            None => return,
+        },
+        _ => return,
+    };
+    e.proto = proto.0;
+    let ports = match proto {
+        IpNextHeaderProtocols::Tcp => TcpPacket::new(&l4).map(|t| (t.get_source(), t.get_destination())),
+        IpNextHeaderProtocols::Udp => UdpPacket::new(&l4).map(|u| (u.get_source(), u.get_destination())),
+        _ => None,
+    };
+    if let Some((sport, dport)) = ports {
+        e.sport = sport;
+        e.dport = dport;
+    }
+}
+
+// ---------------- record ----------------
+
+/// The segment currently being written.
+struct Segment {
+    pcap: PcapWriter<BufWriter<File>>,
+    idx: BufWriter<File>,
+    /// Where the next packet record will start in the pcap file
+    offset: u64,
+    opened: Instant,
+}
+
+fn segment_path(store: &Path, n: u32, ext: &str) -> PathBuf {
+    store.join(format!("seg-{:06}.{}", n, ext))
+}
+
+/// Continue numbering after the segments already in the store.
+fn next_segment_number(store: &Path) -> u32 {
+    list_segments(store).last().map(|(n, _)| n + 1).unwrap_or(1)
+}
+
+fn open_segment(store: &Path, n: u32) -> std::io::Result<Segment> {
+    let pcap_file = BufWriter::new(File::create(segment_path(store, n, "pcap"))?);
+    // Fix the byte order, so `query` knows how to read record headers back.
+    let header = PcapHeader { endianness: Endianness::Little, ..Default::default() };
+    let pcap = PcapWriter::with_header(pcap_file, header).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    let idx = BufWriter::new(File::create(segment_path(store, n, "idx"))?);
+    Ok(Segment { pcap, idx, offset: PCAP_GLOBAL_HEADER, opened: Instant::now() })
+}
+
+fn record(iface_name: &str, store: &Path, promisc: bool, segment_secs: u64, segment_mb: u64) {
+    fs::create_dir_all(store).expect("Failed to create store directory");
+
+    let iface = select::interface_or_exit(iface_name);
+
+    let mut config = Config::default();
+    config.read_timeout = Some(Duration::from_secs(1));
+    config.promiscuous = promisc;
+
+    let mut n = next_segment_number(store);
+    let mut seg = open_segment(store, n).expect("Failed to open segment");
+    let max_bytes = segment_mb * 1024 * 1024;
+    // The query's binary search needs time order; the wall clock can step back (NTP).
+    let mut last_ts = Duration::ZERO;
+
+    match datalink::channel(&iface, config) {
+        Ok(Channel::Ethernet(_tx, mut rx)) => {
+            println!("Recording {} into {}", iface.name, store.display());
+            loop {
+                match rx.next() {
+                    Ok(frame) => {
+                        let ts = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().max(last_ts);
+                        last_ts = ts;
+                        let mut entry = IndexEntry {
+                            ts_us: ts.as_micros() as u64,
+                            offset: seg.offset,
+                            src: Ipv6Addr::UNSPECIFIED,
+                            dst: Ipv6Addr::UNSPECIFIED,
+                            sport: 0,
+                            dport: 0,
+                            proto: 0,
+                            ip_version: 0,
+                            caplen: frame.len() as u32,
+                        };
+                        classify(frame, &mut entry);
+
+                        let packet = PcapPacket::new(ts, frame.len() as u32, frame);
+                        if let Err(e) = seg.pcap.write_packet(&packet) {
+                            eprintln!("pcap write failed: {}", e);
+                            continue;
+                        }
+                        if let Err(e) = seg.idx.write_all(&entry.to_bytes()) {
+                            eprintln!("index write failed: {}", e);
+                        }
+                        // pcap layout is fixed: 16-byte record header + data
+                        seg.offset += PCAP_RECORD_HEADER + frame.len() as u64;
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                    Err(e) => eprintln!("Receive error: {:?}", e),
+                }
+
+                if seg.opened.elapsed() >= Duration::from_secs(segment_secs) || seg.offset >= max_bytes {
+                    let _ = seg.idx.flush();
+                    n += 1;
+                    // Dropping the old writers flushes and closes them.
+                    seg = open_segment(store, n).expect("Failed to open segment");
+                    println!("Started segment {}", n);
+                }
+            }
+        }
+        Ok(_) => eprintln!("Unsupported channel type on this platform"),
+        Err(e) => eprintln!("Failed to open datalink channel: {}", e),
+    }
+}
+
+// ---------------- query ----------------
+
+/// (segment number, path of .idx) sorted by number.
+fn list_segments(store: &Path) -> Vec<(u32, PathBuf)> {
+    let mut segs: Vec<(u32, PathBuf)> = fs::read_dir(store)
+        .map(|dir| {
+            dir.flatten()
+                .filter_map(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    let n = name.strip_prefix("seg-")?.strip_suffix(".idx")?.parse().ok()?;
+                    Some((n, e.path()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    segs.sort();
+    segs
+}
+
+struct Filter {
+    from_us: u64,
+    to_us: u64,
+    host: Option<Ipv6Addr>,
+    port: Option<u16>,
+    proto: Option<u8>,
+}
+
+impl Filter {
+    fn matches(&self, e: &IndexEntry) -> bool {
+        e.ts_us >= self.from_us
+            && e.ts_us <= self.to_us
+            && self.host.map_or(true, |h| e.src == h || e.dst == h)
+            && self.port.map_or(true, |p| e.sport == p || e.dport == p)
+            && self.proto.map_or(true, |p| e.proto == p)
+    }
+}
+
+/// Entry `i` of an index file, read with one seek.
+fn read_entry(idx: &mut File, i: usize) -> std::io::Result<IndexEntry> {
+    let mut b = [0u8; ENTRY_SIZE];
+    idx.seek(SeekFrom::Start((i * ENTRY_SIZE) as u64))?;
+    idx.read_exact(&mut b)?;
+    Ok(IndexEntry::from_bytes(&b))
+}
+
+/// First entry with ts >= from_us (entries are in capture order = time order).
+fn lower_bound(idx: &mut File, count: usize, from_us: u64) -> std::io::Result<usize> {
+    let (mut lo, mut hi) = (0, count);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        if read_entry(idx, mid)?.ts_us < from_us {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    Ok(lo)
+}
+
+fn query(store: &Path, filter: Filter, out: &Path) -> std::io::Result<()> {
+    let mut writer = PcapWriter::new(BufWriter::new(File::create(out)?))
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    let (mut matched, mut scanned, mut skipped_segments) = (0u64, 0u64, 0u64);
+
+    for (n, idx_path) in list_segments(store) {
+        let mut idx = File::open(&idx_path)?;
+        let count = idx.metadata()?.len() as usize / ENTRY_SIZE; // ignore a half-written last entry
+        if count == 0 {
+            continue;
+        }
+        // Whole segment outside the time range? Then don't even open its pcap.
+        // Only the first and last entries are read for that, not the whole index.
+        let first = read_entry(&mut idx, 0)?;
+        let last = read_entry(&mut idx, count - 1)?;
+        if last.ts_us < filter.from_us || first.ts_us > filter.to_us {
+            skipped_segments += 1;
+            continue;
+        }
+
+        let start = lower_bound(&mut idx, count, filter.from_us)?;
+        idx.seek(SeekFrom::Start((start * ENTRY_SIZE) as u64))?;
+        let mut entries = BufReader::new(idx);
+        let mut buf = [0u8; ENTRY_SIZE];
+        let mut pcap: Option<File> = None;
+        for _ in start..count {
+            entries.read_exact(&mut buf)?;
+            let entry = IndexEntry::from_bytes(&buf);
+            if entry.ts_us > filter.to_us {
+                break; // sorted: nothing later can match
+            }
+            scanned += 1;
+            if !filter.matches(&entry) {
+                continue;
+            }
+
+            if pcap.is_none() {
+                pcap = Some(File::open(segment_path(store, n, "pcap"))?);
+            }
+            let file = pcap.as_mut().unwrap();
+            // Jump straight to the record: 16-byte header, then the frame.
+            let mut header = [0u8; PCAP_RECORD_HEADER as usize];
+            let mut data = vec![0u8; entry.caplen as usize];
+            file.seek(SeekFrom::Start(entry.offset))?;
+            match file.read_exact(&mut header).and_then(|_| file.read_exact(&mut data)) {
+                Ok(()) => {}
+                // Live segment: the recorder's pcap buffer isn't on disk yet. That's the end for now.
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e),
+            }
+            let orig_len = u32::from_le_bytes(header[12..16].try_into().unwrap());
+
+            let ts = Duration::from_micros(entry.ts_us);
+            writer
+                .write_packet(&PcapPacket::new(ts, orig_len, &data))
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+            matched += 1;
+        }
+    }
+
+    println!(
+        "{} packets written to {} ({} index entries checked, {} segments skipped by time)",
+        matched,
+        out.display(),
+        scanned,
+        skipped_segments
+    );
+    Ok(())
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Commands::Record { iface, store, promisc, segment_secs, segment_mb } => {
+            record(&iface, &store, promisc, segment_secs, segment_mb);
+        }
+        Commands::Query { store, from, to, host, port, proto, write } => {
+            let filter = Filter {
+                from_us: from.map(|s| (s * 1e6) as u64).unwrap_or(0),
+                to_us: to.map(|s| (s * 1e6) as u64).unwrap_or(u64::MAX),
+                host: host.map(to_v6),
+                port,
+                proto,
+            };
+            if let Err(e) = query(&store, filter, &write) {
+                eprintln!("Query failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+seg.offset += PCAP_RECORD_HEADER + frame.len() as u64;
+```
+
+👉 pcap has a fixed layout: a 24-byte file header, then for each packet a 16-byte record header + the bytes. So we can **compute** where each packet starts while writing, and store that offset in the index.
+
+👉 Segments are written **little-endian** on purpose (`Endianness::Little` in the header), which is why `query` reads `orig_len` with `from_le_bytes`.
+
+---
+
+```rust
+b[16..32].copy_from_slice(&self.src.octets());
+```
+
+👉 Every index entry is exactly **64 bytes**. IPv4 addresses are stored IPv4-mapped (`::ffff:10.0.0.1`) so IPv4 and IPv6 share one layout. Fixed size means entry *i* is at byte `i * 64` — no parsing needed to jump around.
+
+---
+
+```rust
+if last.ts_us < filter.from_us || first.ts_us > filter.to_us { skipped_segments += 1; continue; }
+```
+
+👉 First level of skipping: each segment covers a time range (its first and last entry). Only those two entries are read (two seeks), and segments outside the query range never have their pcap opened.
+
+---
+
+```rust
+fn lower_bound(idx: &mut File, count: usize, from_us: u64) -> std::io::Result<usize>
+```
+
+👉 Second level: inside a segment, entries are in time order, so a **binary search** (one seek per step) finds the first packet ≥ `--from`. From there the index is read sequentially, and we stop at the first packet after `--to`.
+
+👉 "In time order" is guaranteed by `record`: the timestamp is clamped to never go below the previous one (`.max(last_ts)`), so an NTP step backwards can't break the search.
+
+---
+
+```rust
+file.seek(SeekFrom::Start(entry.offset))?;
+```
+
+👉 Third level: only **matching** packets are read from the pcap, each with one seek. A query for one host in a 10 GB store reads the (small) indexes in the time range plus a few KB of pcap.
+
+👉 The segment being recorded can be queried too. Its index may point at packets still sitting in the recorder's pcap `BufWriter`; a short read there just ends that segment.
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: answer "packets between T1 and T2 involving host X on port Y" without scanning gigabytes.
+* **What**:
+
+  1. `record` writes rotating pcap segments plus a fixed-size index (time, 5-tuple, protocol, offset).
+  2. `query` skips segments by time, binary-searches the index, filters by host/port/protocol.
+  3. Matching packets are copied by offset into a new pcap that opens in Wireshark.
+
+---
+
+👉 Example Run:
+
+```sh
+sudo cargo run -- record --iface eth0 --store /var/capture --segment-secs 300
+
+cargo run -- query --store /var/capture \
+  --from $(date -d '14:02' +%s) --to $(date -d '14:05' +%s) \
+  --host 10.2.3.4 --port 443 --write incident.pcap
+```
+
+Output:
+
+```
+Recording eth0 into /var/capture
+Started segment 2
+...
+391 packets written to incident.pcap (1282011 index entries checked, 212 segments skipped by time)
+```
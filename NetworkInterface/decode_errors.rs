@@ -0,0 +1,439 @@
+# 🔹 Big Picture (Why this program exists)
+
+* The first sniffer does this when something doesn't parse:
+
+  ```rust
+  if let Some(eth) = EthernetPacket::new(frame) { ... } else { eprintln!("Malformed ethernet packet"); }
+  if let Some(ipv4) = Ipv4Packet::new(eth.payload()) { ... }   // else: nothing at all
+  ```
+
+* Two problems:
+
+  * pnet's `::new()` only returns `None` — it can't tell you **why** (too short? bad header length?).
+  * The inner layers fail **silently**, and the outer one goes to stderr where nobody counts it.
+
+* Malformed traffic is interesting: broken NIC offload, MTU problems, scanners sending bad IHL / data offset on purpose.
+* This program replaces `Option` with a typed **`DecodeError`**:
+
+  * **layer** – Ethernet / IPv4 / IPv6 / TCP / UDP
+  * **offset** – where in the frame that layer starts
+  * **expected / actual** – how many bytes the header said vs. how many we had
+  * **reason** – truncated header, bad IHL, bad data offset, bad length, unknown version
+
+* Errors are printed **inline** with normal packets and **counted** per layer + reason.
+
+---
+
+# 🔹 Project Layout
+
+```
+sniffer_errors/
+├── Cargo.toml
+└── src/
+    ├── main.rs     <-- capture loop, output, statistics
+    ├── decode.rs   <-- DecodeError + checked decoders
+    └── select.rs   <-- from interface_networks.rs
+```
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+```
+
+---
+
+# 🔹 `src/decode.rs`
+
+```rust
+use pnet::packet::ethernet::{EtherTypes, EthernetPacket};
+use pnet::packet::ip::{IpNextHeaderProtocol, IpNextHeaderProtocols};
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::ipv6::Ipv6Packet;
+use pnet::packet::tcp::TcpPacket;
+use pnet::packet::udp::UdpPacket;
+use std::fmt;
+use std::net::IpAddr;
+
+const ETH_HEADER: usize = 14;
+const IPV4_MIN_HEADER: usize = 20;
+const IPV6_HEADER: usize = 40;
+const TCP_MIN_HEADER: usize = 20;
+const UDP_HEADER: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Layer {
+    Ethernet,
+    Ipv4,
+    Ipv6,
+    Tcp,
+    Udp,
+}
+
+/// Why a layer could not be decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Reason {
+    /// Fewer bytes than the fixed part of the header.
+    TruncatedHeader,
+    /// IPv4 IHL below 5 words, or pointing past the captured bytes.
+    BadIhl(u8),
+    /// TCP data offset below 5 words, or pointing past the segment.
+    BadDataOffset(u8),
+    /// Total / payload / UDP length field disagrees with what we captured.
+    BadLength,
+    /// IP version nibble isn't what the ethertype promised.
+    UnknownVersion(u8),
+}
+
+impl Reason {
+    /// Name without the value, used as a statistics key.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Reason::TruncatedHeader => "truncated header",
+            Reason::BadIhl(_) => "bad IHL",
+            Reason::BadDataOffset(_) => "bad data offset",
+            Reason::BadLength => "bad length",
+            Reason::UnknownVersion(_) => "unknown version",
+        }
+    }
+}
+
+/// A layer that failed to decode, and where.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodeError {
+    pub layer: Layer,
+    /// Byte offset of this layer from the start of the frame.
+    pub offset: usize,
+    /// Bytes the header needed (or claimed).
+    pub expected: usize,
+    /// Bytes that were actually there.
+    pub actual: usize,
+    pub reason: Reason,
+}
+
+impl DecodeError {
+    fn new(layer: Layer, offset: usize, reason: Reason, expected: usize, actual: usize) -> Self {
+        DecodeError { layer, offset, expected, actual, reason }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} @{}: {}", self.layer, self.offset, self.reason.name())?;
+        match self.reason {
+            Reason::BadIhl(v) => write!(f, " ({})", v)?,
+            Reason::BadDataOffset(v) => write!(f, " ({})", v)?,
+            Reason::UnknownVersion(v) => return write!(f, " ({})", v),
+            _ => {}
+        }
+        write!(f, " (expected {} bytes, got {})", self.expected, self.actual)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// What a successfully decoded frame looks like.
+pub struct Summary {
+    pub src: IpAddr,
+    pub dst: IpAddr,
+    pub proto: &'static str,
+    pub ports: Option<(u16, u16)>,
+}
+
+/// Decode Ethernet → IPv4/IPv6 → TCP/UDP.
+/// `Ok(None)` means "valid, but not something we decode" (ARP, ICMP, ...).
+pub fn decode(frame: &[u8]) -> Result<Option<Summary>, DecodeError> {
+    let eth = EthernetPacket::new(frame)
+        .ok_or_else(|| DecodeError::new(Layer::Ethernet, 0, Reason::TruncatedHeader, ETH_HEADER, frame.len()))?;
+    let payload = &frame[ETH_HEADER..];
+
+    let (src, dst, next, l4, l4_offset) = match eth.get_ethertype() {
+        EtherTypes::Ipv4 => {
+            let (ip, l4) = decode_ipv4(payload, ETH_HEADER)?;
+            // From IHL: l4 stops at the total length, so the length difference would include Ethernet padding.
+            let hdr = ip.get_header_length() as usize * 4;
+            // Only the first fragment carries the transport header.
+            if ip.get_fragment_offset() != 0 {
+                return Ok(Some(Summary { src: ip.get_source().into(), dst: ip.get_destination().into(), proto: "IPv4-frag", ports: None }));
+            }
+            (ip.get_source().into(), ip.get_destination().into(), ip.get_next_level_protocol(), l4, ETH_HEADER + hdr)
+        }
+        EtherTypes::Ipv6 => {
+            let (ip, l4) = decode_ipv6(payload, ETH_HEADER)?;
+            (ip.get_source().into(), ip.get_destination().into(), ip.get_next_header(), l4, ETH_HEADER + IPV6_HEADER)
+        }
+        _ => return Ok(None),
+    };
+
+    let (proto, ports) = decode_l4(next, l4, l4_offset)?;
+    Ok(Some(Summary { src, dst, proto, ports }))
+}
+
+/// Check IPv4 header fields before trusting them; returns the packet and its payload
+/// (trimmed to the total length, so Ethernet padding doesn't leak into L4).
+pub fn decode_ipv4(buf: &[u8], offset: usize) -> Result<(Ipv4Packet<'_>, &[u8]), DecodeError> {
+    let err = |reason, expected, actual| DecodeError::new(Layer::Ipv4, offset, reason, expected, actual);
+
+    let ip = Ipv4Packet::new(buf).ok_or_else(|| err(Reason::TruncatedHeader, IPV4_MIN_HEADER, buf.len()))?;
+    if ip.get_version() != 4 {
+        return Err(err(Reason::UnknownVersion(ip.get_version()), IPV4_MIN_HEADER, buf.len()));
+    }
+    let ihl = ip.get_header_length();
+    let hdr = ihl as usize * 4;
+    if hdr < IPV4_MIN_HEADER || hdr > buf.len() {
+        return Err(err(Reason::BadIhl(ihl), hdr, buf.len()));
+    }
+    let total = ip.get_total_length() as usize;
+    if total < hdr || total > buf.len() {
+        return Err(err(Reason::BadLength, total, buf.len()));
+    }
+    Ok((ip, &buf[hdr..total]))
+}
+
+pub fn decode_ipv6(buf: &[u8], offset: usize) -> Result<(Ipv6Packet<'_>, &[u8]), DecodeError> {
+    let err = |reason, expected, actual| DecodeError::new(Layer::Ipv6, offset, reason, expected, actual);
+
+    let ip = Ipv6Packet::new(buf).ok_or_else(|| err(Reason::TruncatedHeader, IPV6_HEADER, buf.len()))?;
+    if ip.get_version() != 6 {
+        return Err(err(Reason::UnknownVersion(ip.get_version()), IPV6_HEADER, buf.len()));
+    }
+    let end = IPV6_HEADER + ip.get_payload_length() as usize;
+    if end > buf.len() {
+        return Err(err(Reason::BadLength, end, buf.len()));
+    }
+    Ok((ip, &buf[IPV6_HEADER..end]))
+}
+
+/// TCP and UDP only; other protocols are reported by name without ports.
+pub fn decode_l4(
+    proto: IpNextHeaderProtocol,
+    buf: &[u8],
+    offset: usize,
+) -> Result<(&'static str, Option<(u16, u16)>), DecodeError> {
+    match proto {
+        IpNextHeaderProtocols::Tcp => {
+            let err = |reason, expected, actual| DecodeError::new(Layer::Tcp, offset, reason, expected, actual);
+            let tcp = TcpPacket::new(buf).ok_or_else(|| err(Reason::TruncatedHeader, TCP_MIN_HEADER, buf.len()))?;
+            let data_offset = tcp.get_data_offset();
+            let hdr = data_offset as usize * 4;
+            if hdr < TCP_MIN_HEADER || hdr > buf.len() {
+                return Err(err(Reason::BadDataOffset(data_offset), hdr, buf.len()));
+            }
+            Ok(("TCP", Some((tcp.get_source(), tcp.get_destination()))))
+        }
+        IpNextHeaderProtocols::Udp => {
+            let err = |reason, expected, actual| DecodeError::new(Layer::Udp, offset, reason, expected, actual);
+            let udp = UdpPacket::new(buf).ok_or_else(|| err(Reason::TruncatedHeader, UDP_HEADER, buf.len()))?;
+            let len = udp.get_length() as usize;
+            if len < UDP_HEADER || len > buf.len() {
+                return Err(err(Reason::BadLength, len, buf.len()));
+            }
+            Ok(("UDP", Some((udp.get_source(), udp.get_destination()))))
+        }
+        IpNextHeaderProtocols::Icmp => Ok(("ICMP", None)),
+        IpNextHeaderProtocols::Icmpv6 => Ok(("ICMPv6", None)),
+        _ => Ok(("IP", None)),
+    }
+}
+```
+
+---
+
+# 🔹 `src/main.rs`
+
+```rust
+mod decode;
+mod select;
+
+use clap::Parser;
+use decode::{decode, DecodeError, Layer};
+use pnet::datalink::{self, Channel, Config};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+#[derive(Parser)]
+struct Cli {
+    #[arg(long)]
+    iface: String,
+    #[arg(long)]
+    promisc: bool,
+    /// Only print malformed frames (statistics still count everything)
+    #[arg(long)]
+    errors_only: bool,
+    /// Print statistics every N seconds (0 = never)
+    #[arg(long, default_value_t = 10)]
+    stats_every: u64,
+}
+
+#[derive(Default)]
+struct Stats {
+    frames: u64,
+    decoded: u64,
+    skipped: u64,
+    /// (layer, reason) -> count
+    errors: BTreeMap<(Layer, &'static str), u64>,
+}
+
+impl Stats {
+    fn record_error(&mut self, err: &DecodeError) {
+        *self.errors.entry((err.layer, err.reason.name())).or_insert(0) += 1;
+    }
+
+    fn malformed(&self) -> u64 {
+        self.errors.values().sum()
+    }
+
+    fn print(&self) {
+        println!(
+            "--- frames={} decoded={} skipped={} malformed={}",
+            self.frames,
+            self.decoded,
+            self.skipped,
+            self.malformed()
+        );
+        for ((layer, reason), count) in &self.errors {
+            println!("    {:<9} {:<17} {:>8}", format!("{:?}", layer), reason, count);
+        }
+    }
+}
+
+/// First bytes of the failing layer, so the cause is visible without a pcap.
+fn hex_at(frame: &[u8], offset: usize, n: usize) -> String {
+    frame.iter().skip(offset).take(n).map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(" ")
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let iface = select::interface_or_exit(&cli.iface);
+
+    let mut config = Config::default();
+    config.read_timeout = Some(Duration::from_secs(1));
+    config.promiscuous = cli.promisc;
+
+    let mut stats = Stats::default();
+    let mut last_report = Instant::now();
+
+    match datalink::channel(&iface, config) {
+        Ok(Channel::Ethernet(_tx, mut rx)) => {
+            println!("Listening on {}", iface.name);
+            loop {
+                match rx.next() {
+                    Ok(frame) => {
+                        stats.frames += 1;
+                        match decode(frame) {
+                            Ok(Some(s)) => {
+                                stats.decoded += 1;
+                                if !cli.errors_only {
+                                    match s.ports {
+                                        Some((sp, dp)) => println!("{} {}:{} -> {}:{}", s.proto, s.src, sp, s.dst, dp),
+                                        None => println!("{} {} -> {}", s.proto, s.src, s.dst),
+                                    }
+                                }
+                            }
+                            Ok(None) => stats.skipped += 1,
+                            Err(err) => {
+                                stats.record_error(&err);
+                                println!("MALFORMED len={} {} [{}]", frame.len(), err, hex_at(frame, err.offset, 16));
+                            }
+                        }
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                    Err(e) => eprintln!("Receive error: {:?}", e),
+                }
+
+                if cli.stats_every > 0 && last_report.elapsed() >= Duration::from_secs(cli.stats_every) {
+                    stats.print();
+                    last_report = Instant::now();
+                }
+            }
+        }
+        Ok(_) => eprintln!("Unsupported channel type on this platform"),
+        Err(e) => eprintln!("Failed to open datalink channel: {}", e),
+    }
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+pub struct DecodeError { pub layer: Layer, pub offset: usize, pub expected: usize, pub actual: usize, pub reason: Reason }
+```
+
+👉 Everything you need to understand a broken frame **without** opening Wireshark: which header, where it starts, and how far off the numbers are.
+
+👉 `Reason` is an enum, so the code can `match` on it; `reason.name()` drops the value so `BadIhl(3)` and `BadIhl(4)` land in the same statistics bucket.
+
+---
+
+```rust
+let hdr = ihl as usize * 4;
+if hdr < IPV4_MIN_HEADER || hdr > buf.len() {
+    return Err(err(Reason::BadIhl(ihl), hdr, buf.len()));
+}
+```
+
+👉 `Ipv4Packet::new` only checks for **20 bytes**. It happily accepts IHL = 2 or IHL = 15 on a 24-byte packet, and `payload()` then returns garbage. We check the field ourselves before using it. TCP's **data offset** gets the same treatment.
+
+---
+
+```rust
+if total < hdr || total > buf.len() { return Err(err(Reason::BadLength, total, buf.len())); }
+Ok((ip, &buf[hdr..total]))
+```
+
+👉 A total length **bigger** than what we captured means truncation (snaplen, MTU). **Smaller** is normal: Ethernet pads short frames to 60 bytes, so we cut the payload at `total` instead of passing the padding on to TCP/UDP.
+
+---
+
+```rust
+if ip.get_fragment_offset() != 0 { return Ok(Some(Summary { proto: "IPv4-frag", ports: None, .. })); }
+```
+
+👉 Later fragments have **no** TCP/UDP header. Without this check they'd show up as "TCP bad data offset" — a false positive.
+
+---
+
+```rust
+Err(err) => {
+    stats.record_error(&err);
+    println!("MALFORMED len={} {} [{}]", frame.len(), err, hex_at(frame, err.offset, 16));
+}
+```
+
+👉 Malformed frames go to **stdout**, in the same stream as good packets, so `grep MALFORMED` or a log shipper picks them up. The hex dump starts at the failing layer.
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: `Option` + `eprintln!` loses *why* a packet was bad, and inner-layer failures vanish completely.
+* **What**:
+
+  1. `DecodeError` with layer, offset, expected/actual length and a `Reason` enum.
+  2. Checked decoders for IPv4 (IHL, total length), IPv6 (payload length), TCP (data offset), UDP (length).
+  3. Malformed frames printed inline with a hex snippet.
+  4. Per-layer, per-reason counters in the periodic stats.
+
+---
+
+👉 Example Run:
+
+```sh
+sudo cargo run -- --iface eth0 --promisc --stats-every 30
+```
+
+Output:
+
+```
+Listening on eth0
+TCP 192.168.1.10:54321 -> 140.82.112.3:443
+UDP 192.168.1.10:53211 -> 192.168.1.1:53
+MALFORMED len=60 Ipv4 @14: bad IHL (3) (expected 12 bytes, got 46) [43 00 00 2e 1a 2b 00 00 40 06 ...]
+MALFORMED len=54 Tcp @34: bad data offset (15) (expected 60 bytes, got 20) [d4 31 00 50 ...]
+--- frames=18233 decoded=18102 skipped=129 malformed=2
+    Ipv4      bad IHL                  1
+    Tcp       bad data offset          1
+```
@@ -0,0 +1,410 @@
+# 🔹 Big Picture (Why this program exists)
+
+* `datalink::channel()` opens an `AF_PACKET` socket, and that needs **root** or **`CAP_NET_RAW`**.
+* The first sniffer keeps those privileges **for its whole life** — while it parses bytes that *anyone on the network* can send. One parser bug (a bad slice, a decoder in a C library) and the attacker runs code **as root**.
+* But the privilege is only needed for **one call**. After the socket is open, the file descriptor keeps working no matter who we are.
+* So, right after opening the channel, this program:
+
+  1. switches to an unprivileged **user and group** (and drops supplementary groups),
+  2. drops **all capabilities**, including the bounding set, and sets `no_new_privs`,
+  3. optionally **chroots** into an empty directory,
+  4. installs a **seccomp allow-list**: only the handful of syscalls the capture loop needs.
+
+* Each step is verified; if one fails, the program **exits** instead of capturing with privileges it shouldn't have.
+
+---
+
+# 🔹 Project Layout
+
+```
+sniffer_privdrop/
+├── Cargo.toml
+└── src/
+    ├── main.rs       <-- open channel, drop privileges, capture loop
+    ├── privdrop.rs   <-- user/group switch, capabilities, chroot, seccomp
+    └── select.rs     <-- interface lookup, from interface_networks.rs
+```
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+nix = { version = "0.27", features = ["user", "fs"] }
+caps = "0.5"
+seccompiler = "0.4"
+libc = "0.2"
+```
+
+---
+
+# 🔹 `src/privdrop.rs`
+
+```rust
+use caps::CapSet;
+use nix::unistd::{self, Gid, Uid, User};
+use seccompiler::{
+    BackendError, BpfProgram, SeccompAction, SeccompCmpArgLen, SeccompCmpOp, SeccompCondition, SeccompFilter, SeccompRule,
+};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub struct Options<'a> {
+    /// Account to run as after the socket is open (ignored when not started as root).
+    pub user: &'a str,
+    /// Empty directory to chroot into.
+    pub chroot: Option<&'a Path>,
+    /// Install the seccomp allow-list.
+    pub seccomp: bool,
+    /// Log blocked syscalls instead of failing them (to find what's missing from the list).
+    pub seccomp_log: bool,
+}
+
+/// Drop everything we don't need anymore. Call this *after* the capture socket is open
+/// and *before* the first packet is parsed. Returns the uid we end up running as.
+pub fn drop_privileges(opts: &Options) -> Result<Uid, String> {
+    let as_root = Uid::effective().is_root();
+
+    // Look up the user while /etc/passwd is still reachable (before chroot).
+    let user = if as_root {
+        Some(
+            User::from_name(opts.user)
+                .map_err(|e| format!("lookup user {}: {}", opts.user, e))?
+                .ok_or_else(|| format!("no such user: {}", opts.user))?,
+        )
+    } else {
+        None
+    };
+
+    // Bounding set: removes capabilities for good, even across execve().
+    // Needs CAP_SETPCAP, so it has to happen while we are still root.
+    if as_root {
+        for cap in caps::runtime::thread_all_supported() {
+            caps::drop(None, CapSet::Bounding, cap).map_err(|e| format!("drop bounding {}: {}", cap, e))?;
+        }
+    }
+
+    // chroot needs CAP_SYS_CHROOT, so also before the user switch.
+    if let Some(dir) = opts.chroot {
+        unistd::chroot(dir).map_err(|e| format!("chroot {}: {}", dir.display(), e))?;
+        unistd::chdir("/").map_err(|e| format!("chdir /: {}", e))?;
+    }
+
+    if let Some(user) = &user {
+        // Order matters: groups first (needs root), then gid, then uid last.
+        unistd::setgroups(&[user.gid]).map_err(|e| format!("setgroups: {}", e))?;
+        unistd::setresgid(user.gid, user.gid, user.gid).map_err(|e| format!("setresgid: {}", e))?;
+        unistd::setresuid(user.uid, user.uid, user.uid).map_err(|e| format!("setresuid: {}", e))?;
+    }
+
+    // Started as root: setuid already emptied these. Started with file capabilities
+    // (setcap cap_net_raw+ep): this is where CAP_NET_RAW goes away.
+    for set in [CapSet::Effective, CapSet::Permitted, CapSet::Inheritable, CapSet::Ambient] {
+        caps::clear(None, set).map_err(|e| format!("clear {:?}: {}", set, e))?;
+    }
+
+    // Neither setuid binaries nor file capabilities can give anything back after this.
+    // Also required for installing seccomp without CAP_SYS_ADMIN.
+    if unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) } != 0 {
+        return Err(format!("PR_SET_NO_NEW_PRIVS: {}", std::io::Error::last_os_error()));
+    }
+
+    verify(user.as_ref().map(|u| (u.uid, u.gid)))?;
+    // Read before seccomp: getuid() isn't on the allow-list.
+    let uid = Uid::current();
+
+    if opts.seccomp {
+        install_seccomp(opts.seccomp_log)?;
+    }
+    Ok(uid)
+}
+
+/// Don't trust the calls above: check that root is really gone.
+fn verify(expected: Option<(Uid, Gid)>) -> Result<(), String> {
+    if let Some((uid, gid)) = expected {
+        if Uid::current() != uid || Uid::effective() != uid || Gid::current() != gid || Gid::effective() != gid {
+            return Err("uid/gid did not change".into());
+        }
+        // If this succeeds we could become root again: the saved set-user-ID was not dropped.
+        if unistd::setuid(Uid::from_raw(0)).is_ok() {
+            return Err("regained root after dropping it".into());
+        }
+    }
+    let left = caps::read(None, CapSet::Permitted).map_err(|e| e.to_string())?;
+    if !left.is_empty() {
+        return Err(format!("capabilities still permitted: {:?}", left));
+    }
+    Ok(())
+}
+
+/// Syscalls the capture loop needs. Everything else — execve, open, socket, ptrace,
+/// connect ... — fails with EPERM, even if an attacker gets code execution in the parser.
+fn allowed_syscalls() -> Vec<i64> {
+    let mut list = vec![
+        // packet socket: pnet polls with the read timeout, then receives
+        libc::SYS_recvfrom,
+        libc::SYS_recvmsg,
+        libc::SYS_ppoll,
+        // memory allocator
+        libc::SYS_brk,
+        libc::SYS_mmap,
+        libc::SYS_munmap,
+        libc::SYS_mremap,
+        libc::SYS_madvise,
+        // runtime: locks, signals, clocks, exit
+        libc::SYS_futex,
+        libc::SYS_sigaltstack,
+        libc::SYS_rt_sigreturn,
+        libc::SYS_rt_sigprocmask,
+        libc::SYS_clock_gettime,
+        libc::SYS_clock_nanosleep,
+        libc::SYS_sched_yield,
+        libc::SYS_getrandom,
+        libc::SYS_close,
+        libc::SYS_exit,
+        libc::SYS_exit_group,
+    ];
+    // Newer architectures (aarch64, riscv64) only have ppoll.
+    #[cfg(target_arch = "x86_64")]
+    list.push(libc::SYS_poll);
+    list
+}
+
+/// One rule per allowed fd; rules for a syscall are OR'ed. Argument 0 of write/writev is the fd.
+fn stdout_stderr_only() -> Result<Vec<SeccompRule>, BackendError> {
+    [1u64, 2]
+        .into_iter()
+        .map(|fd| SeccompRule::new(vec![SeccompCondition::new(0, SeccompCmpArgLen::Dword, SeccompCmpOp::Eq, fd)?]))
+        .collect()
+}
+
+fn install_seccomp(log_only: bool) -> Result<(), String> {
+    // An empty rule list = "allow this syscall whatever its arguments".
+    let mut rules: BTreeMap<i64, Vec<SeccompRule>> = allowed_syscalls().into_iter().map(|nr| (nr, Vec::new())).collect();
+    // write() on the packet socket would *send* frames: only stdout / stderr are allowed.
+    for nr in [libc::SYS_write, libc::SYS_writev] {
+        rules.insert(nr, stdout_stderr_only().map_err(|e| e.to_string())?);
+    }
+
+    let mismatch = if log_only { SeccompAction::Log } else { SeccompAction::Errno(libc::EPERM as u32) };
+    let arch: seccompiler::TargetArch = std::env::consts::ARCH.try_into().map_err(|e| format!("seccomp arch: {:?}", e))?;
+    let filter = SeccompFilter::new(rules, mismatch, SeccompAction::Allow, arch).map_err(|e| e.to_string())?;
+    let program: BpfProgram = filter.try_into().map_err(|e: BackendError| e.to_string())?;
+    seccompiler::apply_filter(&program).map_err(|e| format!("apply seccomp: {}", e))
+}
+```
+
+---
+
+# 🔹 `src/main.rs`
+
+```rust
+mod privdrop;
+mod select;
+
+use clap::Parser;
+use pnet::datalink::{self, Channel, Config};
+use pnet::packet::ethernet::{EtherTypes, EthernetPacket};
+use pnet::packet::ip::IpNextHeaderProtocols;
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::tcp::TcpPacket;
+use pnet::packet::Packet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Parser)]
+struct Cli {
+    #[arg(long)]
+    iface: String,
+    #[arg(long)]
+    promisc: bool,
+    /// Run as this user once the capture socket is open
+    #[arg(long, default_value = "nobody")]
+    user: String,
+    /// chroot into this (empty, root-owned) directory after opening the socket
+    #[arg(long)]
+    chroot: Option<PathBuf>,
+    /// Don't install the seccomp allow-list
+    #[arg(long)]
+    no_seccomp: bool,
+    /// Log syscalls outside the allow-list instead of blocking them
+    #[arg(long)]
+    seccomp_log: bool,
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let iface = select::interface_or_exit(&cli.iface);
+
+    let mut config = Config::default();
+    config.read_timeout = Some(Duration::from_secs(1));
+    config.promiscuous = cli.promisc;
+
+    // The only privileged operation in the whole program.
+    let (_tx, mut rx) = match datalink::channel(&iface, config) {
+        Ok(Channel::Ethernet(tx, rx)) => (tx, rx),
+        Ok(_) => {
+            eprintln!("Unsupported channel type on this platform");
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to open datalink channel: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let opts = privdrop::Options {
+        user: &cli.user,
+        chroot: cli.chroot.as_deref(),
+        seccomp: !cli.no_seccomp,
+        seccomp_log: cli.seccomp_log,
+    };
+    let uid = match privdrop::drop_privileges(&opts) {
+        Ok(uid) => uid,
+        Err(e) => {
+            // Refuse to parse untrusted packets with privileges we failed to drop.
+            eprintln!("Failed to drop privileges: {}", e);
+            std::process::exit(1);
+        }
+    };
+    eprintln!(
+        "Listening on {} as uid={} (no capabilities{}{})",
+        iface.name,
+        uid,
+        if opts.chroot.is_some() { ", chrooted" } else { "" },
+        if opts.seccomp { ", seccomp" } else { "" }
+    );
+
+    loop {
+        match rx.next() {
+            Ok(frame) => {
+                if let Some(eth) = EthernetPacket::new(frame) {
+                    if eth.get_ethertype() == EtherTypes::Ipv4 {
+                        if let Some(ipv4) = Ipv4Packet::new(eth.payload()) {
+                            println!("IPv4: {} -> {}", ipv4.get_source(), ipv4.get_destination());
+                            if ipv4.get_next_level_protocol() == IpNextHeaderProtocols::Tcp {
+                                if let Some(tcp) = TcpPacket::new(ipv4.payload()) {
+                                    println!("  TCP: {} -> {}", tcp.get_source(), tcp.get_destination());
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    eprintln!("Malformed ethernet packet");
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+            Err(e) => eprintln!("Receive error: {:?}", e),
+        }
+    }
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+let (_tx, mut rx) = match datalink::channel(&iface, config) { ... };
+...
+privdrop::drop_privileges(&opts)
+```
+
+👉 The kernel checks permissions when a socket is **created**, not on every `recv`. Once `rx` exists, we can throw away root and it keeps delivering packets.
+
+👉 `_tx` is kept alive on purpose: dropping it doesn't close the shared socket, but it keeps the example identical to the original sniffer.
+
+---
+
+```rust
+unistd::setgroups(&[user.gid])?;
+unistd::setresgid(user.gid, user.gid, user.gid)?;
+unistd::setresuid(user.uid, user.uid, user.uid)?;
+```
+
+👉 The order is fixed: after `setuid` we are no longer allowed to change groups. Forgetting `setgroups` keeps root's supplementary groups (e.g. `disk`, `adm`).
+
+👉 `setres*id` sets **real, effective and saved** IDs. Plain `seteuid` would leave the saved ID at 0, and the process could switch back — `verify()` tests exactly that.
+
+---
+
+```rust
+for cap in caps::runtime::thread_all_supported() { caps::drop(None, CapSet::Bounding, cap)?; }
+for set in [Effective, Permitted, Inheritable, Ambient] { caps::clear(None, set)?; }
+```
+
+👉 Capabilities are root's powers split into pieces. The **bounding** set limits what could ever come back; **permitted/effective** are what we have now. When started via `setcap cap_net_raw+ep` (no root), this is the step that removes `CAP_NET_RAW`.
+
+---
+
+```rust
+libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0)
+```
+
+👉 After this, `execve` of a setuid program (`sudo`, `su`) gives **no** extra privileges. The kernel also requires it before an unprivileged process may install a seccomp filter.
+
+---
+
+```rust
+SeccompFilter::new(rules, SeccompAction::Errno(EPERM), SeccompAction::Allow, arch)
+```
+
+👉 The filter runs in the kernel for **every** syscall: on the list → allowed, anything else → `EPERM`. No `open`, `socket`, `connect`, `execve` — an exploited parser can't read files, phone home, or start a shell.
+
+👉 `write` / `writev` are allowed **only for fd 1 and 2** (a `SeccompCondition` on argument 0). Unrestricted `write` would let injected code send forged frames out of the still-open packet socket.
+
+👉 The list is short because the loop only receives, prints and allocates. If you add features (writing pcaps, DNS lookups) the filter will block them — run once with `--seccomp-log` and look for `audit: type=1326` lines in `dmesg` to see which syscalls are missing.
+
+---
+
+```rust
+unistd::chroot(dir)?; unistd::chdir("/")?;
+```
+
+👉 Extra layer for the case where seccomp is disabled: the process sees an empty directory as `/`. Use a root-owned, empty directory (`/var/empty`), not one the unprivileged user can write to.
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: root is only needed to open the capture socket, not to parse packets. Keeping it turns any parser bug into a root compromise.
+* **What**:
+
+  1. Open the channel while privileged.
+  2. Drop the bounding set, chroot, then switch groups, gid and uid (real, effective and saved).
+  3. Clear all capability sets and set `no_new_privs`.
+  4. Verify that root can't be regained.
+  5. Install a seccomp allow-list for the capture loop.
+  6. Exit if any of this fails.
+
+---
+
+👉 Example Run:
+
+```sh
+sudo mkdir -p /var/empty
+sudo cargo run -- --iface eth0 --user nobody --chroot /var/empty
+
+# or without sudo at all: give only CAP_NET_RAW to the binary
+sudo setcap cap_net_raw+ep target/debug/sniffer_privdrop
+./target/debug/sniffer_privdrop --iface eth0
+```
+
+Output:
+
+```
+Listening on eth0 as uid=65534 (no capabilities, chrooted, seccomp)
+IPv4: 192.168.1.10 -> 140.82.112.3
+  TCP: 54321 -> 443
+```
+
+Check from another terminal:
+
+```sh
+grep -E 'Uid|Gid|Cap(Prm|Eff|Bnd)|NoNewPrivs|Seccomp' /proc/$(pgrep sniffer_privdrop)/status
+# Uid:    65534   65534   65534   65534
+# CapPrm: 0000000000000000
+# CapBnd: 0000000000000000
+# NoNewPrivs:     1
+# Seccomp:        2
+```
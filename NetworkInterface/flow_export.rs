@@ -0,0 +1,645 @@
+# 🔹 Big Picture (Why this program exists)
+
+* The VLAN lesson (`vlan_mpls_decode.rs`) introduced a **flow table** (`flow.rs`): packets and bytes per conversation.
+* Our flow-analysis stack already understands **NetFlow v5** and **IPFIX** (NetFlow v10), the standard formats routers use to report flows.
+* This program turns the sniffer into a tiny **flow exporter**:
+
+  1. Keep counting flows in `FlowTable`.
+  2. **Expire** flows with two timeouts:
+     * **inactive** – no packet for N seconds → the conversation is over.
+     * **active** – flow has been running for N seconds → report it anyway (long downloads shouldn't be invisible for hours).
+  3. Encode expired flows as **NetFlow v5** or **IPFIX** datagrams (IPFIX with templates for IPv4 **and** IPv6).
+  4. Send them to a **UDP collector**, or append them to a **file**.
+
+---
+
+# 🔹 Project Layout
+
+```
+flow_exporter/
+├── Cargo.toml
+└── src/
+    ├── main.rs     <-- capture loop + exporter
+    ├── flow.rs     <-- from the VLAN lesson, extended below
+    └── select.rs   <-- from interface_networks.rs
+```
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+```
+
+---
+
+# 🔹 `src/flow.rs` (extended)
+
+Two additions to the VLAN lesson's version: flows remember their **TCP flags**, and the table can **expire** flows.
+
+```rust
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FlowKey {
+    pub vlan: Option<u16>,
+    pub src: IpAddr,
+    pub dst: IpAddr,
+    pub proto: u8,
+    pub sport: u16,
+    pub dport: u16,
+}
+
+#[derive(Clone, Debug)]
+pub struct FlowStats {
+    pub packets: u64,
+    pub bytes: u64,
+    pub first_seen: Instant,
+    pub last_seen: Instant,
+    /// OR of all TCP flags seen (0 for non-TCP)
+    pub tcp_flags: u8,
+}
+
+#[derive(Default)]
+pub struct FlowTable {
+    pub flows: HashMap<FlowKey, FlowStats>,
+}
+
+impl FlowTable {
+    pub fn update(&mut self, key: FlowKey, bytes: usize, tcp_flags: u8) {
+        let now = Instant::now();
+        let stats = self.flows.entry(key).or_insert(FlowStats {
+            packets: 0,
+            bytes: 0,
+            first_seen: now,
+            last_seen: now,
+            tcp_flags: 0,
+        });
+        stats.packets += 1;
+        stats.bytes += bytes as u64;
+        stats.last_seen = now;
+        stats.tcp_flags |= tcp_flags;
+    }
+
+    /// Remove and return flows that hit the inactive or active timeout.
+    pub fn expire(&mut self, now: Instant, active: Duration, inactive: Duration) -> Vec<(FlowKey, FlowStats)> {
+        let mut expired = Vec::new();
+        self.flows.retain(|key, stats| {
+            let idle = now.duration_since(stats.last_seen) >= inactive;
+            let long = now.duration_since(stats.first_seen) >= active;
+            if idle || long {
+                expired.push((*key, stats.clone()));
+                false
+            } else {
+                true
+            }
+        });
+        expired
+    }
+}
+```
+
+---
+
+# 🔹 `src/main.rs`
+
+```rust
+mod flow;
+mod select;
+
+use clap::{Parser, ValueEnum};
+use flow::{FlowKey, FlowStats, FlowTable};
+use pnet::datalink::{self, Channel, Config};
+use pnet::packet::ethernet::{EtherTypes, EthernetPacket};
+use pnet::packet::ip::{IpNextHeaderProtocol, IpNextHeaderProtocols};
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::ipv6::Ipv6Packet;
+use pnet::packet::tcp::TcpPacket;
+use pnet::packet::udp::UdpPacket;
+use pnet::packet::vlan::VlanPacket;
+use pnet::packet::Packet;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::net::{IpAddr, UdpSocket};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    V5,
+    Ipfix,
+}
+
+#[derive(Parser)]
+struct Cli {
+    #[arg(long)]
+    iface: String,
+    #[arg(long)]
+    promisc: bool,
+    /// Export format
+    #[arg(long, value_enum, default_value = "ipfix")]
+    format: Format,
+    /// UDP collector, e.g. 127.0.0.1:2055
+    #[arg(long)]
+    collector: Option<String>,
+    /// Append datagrams to this file instead of (or as well as) sending them
+    #[arg(long)]
+    write: Option<String>,
+    /// Report long-running flows after this many seconds
+    #[arg(long, default_value_t = 60)]
+    active_timeout: u64,
+    /// Flows idle this long are finished
+    #[arg(long, default_value_t = 15)]
+    inactive_timeout: u64,
+    /// IPFIX observation domain / NetFlow engine id
+    #[arg(long, default_value_t = 1)]
+    domain: u32,
+}
+
+/// Largest datagram we send: 1500-byte MTU minus IPv4 (20) and UDP (8) headers.
+const MAX_DATAGRAM: usize = 1472;
+
+// ---------- NetFlow v5 ----------
+
+/// 24 + 30 * 48 = 1464 bytes, just under MAX_DATAGRAM.
+const V5_MAX_RECORDS: usize = 30;
+
+/// NetFlow v5 (fixed format, IPv4 only). 24-byte header + 48 bytes per record.
+struct V5Encoder {
+    sequence: u32,
+    engine_id: u8,
+}
+
+impl V5Encoder {
+    fn encode(&mut self, flows: &[&(FlowKey, FlowStats)], clock: &Clock) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(24 + 48 * flows.len());
+        let now = clock.unix();
+        buf.extend_from_slice(&5u16.to_be_bytes()); // version
+        buf.extend_from_slice(&(flows.len() as u16).to_be_bytes()); // count
+        buf.extend_from_slice(&clock.uptime_ms(Instant::now()).to_be_bytes()); // sys_uptime
+        buf.extend_from_slice(&(now.as_secs() as u32).to_be_bytes()); // unix_secs
+        buf.extend_from_slice(&now.subsec_nanos().to_be_bytes()); // unix_nsecs
+        buf.extend_from_slice(&self.sequence.to_be_bytes()); // flow_sequence
+        buf.push(0); // engine_type
+        buf.push(self.engine_id); // engine_id
+        buf.extend_from_slice(&0u16.to_be_bytes()); // sampling_interval
+
+        for (key, stats) in flows.iter().map(|f| (&f.0, &f.1)) {
+            let (src, dst) = match (key.src, key.dst) {
+                (IpAddr::V4(s), IpAddr::V4(d)) => (s, d),
+                _ => continue, // filtered out by the caller
+            };
+            buf.extend_from_slice(&src.octets());
+            buf.extend_from_slice(&dst.octets());
+            buf.extend_from_slice(&[0; 4]); // nexthop
+            buf.extend_from_slice(&0u16.to_be_bytes()); // input ifindex
+            buf.extend_from_slice(&0u16.to_be_bytes()); // output ifindex
+            buf.extend_from_slice(&(stats.packets.min(u32::MAX as u64) as u32).to_be_bytes());
+            buf.extend_from_slice(&(stats.bytes.min(u32::MAX as u64) as u32).to_be_bytes());
+            buf.extend_from_slice(&clock.uptime_ms(stats.first_seen).to_be_bytes());
+            buf.extend_from_slice(&clock.uptime_ms(stats.last_seen).to_be_bytes());
+            buf.extend_from_slice(&key.sport.to_be_bytes());
+            buf.extend_from_slice(&key.dport.to_be_bytes());
+            buf.push(0); // pad1
+            buf.push(stats.tcp_flags);
+            buf.push(key.proto);
+            buf.push(0); // tos
+            buf.extend_from_slice(&[0; 4]); // src_as, dst_as
+            buf.extend_from_slice(&[0; 2]); // src_mask, dst_mask
+            buf.extend_from_slice(&[0; 2]); // pad2
+        }
+        self.sequence = self.sequence.wrapping_add(flows.len() as u32);
+        buf
+    }
+}
+
+// ---------- IPFIX ----------
+
+const TEMPLATE_V4: u16 = 256;
+const TEMPLATE_V6: u16 = 257;
+/// Resend templates every N messages: over UDP the collector may have missed them.
+const TEMPLATE_EVERY: u32 = 20;
+
+/// (information element id, length) — IANA IPFIX registry numbers.
+const FIELDS_V4: &[(u16, u16)] = &[
+    (8, 4),   // sourceIPv4Address
+    (12, 4),  // destinationIPv4Address
+    (7, 2),   // sourceTransportPort
+    (11, 2),  // destinationTransportPort
+    (4, 1),   // protocolIdentifier
+    (6, 1),   // tcpControlBits
+    (58, 2),  // vlanId
+    (2, 8),   // packetDeltaCount
+    (1, 8),   // octetDeltaCount
+    (152, 8), // flowStartMilliseconds
+    (153, 8), // flowEndMilliseconds
+];
+
+const FIELDS_V6: &[(u16, u16)] = &[
+    (27, 16), // sourceIPv6Address
+    (28, 16), // destinationIPv6Address
+    (7, 2),
+    (11, 2),
+    (4, 1),
+    (6, 1),
+    (58, 2),
+    (2, 8),
+    (1, 8),
+    (152, 8),
+    (153, 8),
+];
+
+const IPFIX_HEADER: usize = 16;
+const SET_HEADER: usize = 4;
+
+/// Bytes of one data record / one template record for a field list.
+fn record_len(fields: &[(u16, u16)]) -> usize {
+    fields.iter().map(|&(_, len)| len as usize).sum()
+}
+fn template_len(fields: &[(u16, u16)]) -> usize {
+    4 + 4 * fields.len()
+}
+
+/// How many flows from `start` on fit in one IPFIX message of at most MAX_DATAGRAM bytes.
+/// Assumes the worst case: templates included and both data sets present.
+fn ipfix_batch_end(flows: &[&(FlowKey, FlowStats)], start: usize) -> usize {
+    let mut size = IPFIX_HEADER + SET_HEADER + template_len(FIELDS_V4) + template_len(FIELDS_V6) + 2 * SET_HEADER;
+    let mut end = start;
+    while end < flows.len() {
+        size += record_len(if flows[end].0.src.is_ipv4() { FIELDS_V4 } else { FIELDS_V6 });
+        if size > MAX_DATAGRAM {
+            break;
+        }
+        end += 1;
+    }
+    end.max(start + 1)
+}
+
+struct IpfixEncoder {
+    sequence: u32,
+    domain: u32,
+    messages: u32,
+}
+
+impl IpfixEncoder {
+    fn template_set(buf: &mut Vec<u8>) {
+        let start = buf.len();
+        buf.extend_from_slice(&2u16.to_be_bytes()); // set id 2 = template set
+        buf.extend_from_slice(&0u16.to_be_bytes()); // length, patched below
+        for (id, fields) in [(TEMPLATE_V4, FIELDS_V4), (TEMPLATE_V6, FIELDS_V6)] {
+            buf.extend_from_slice(&id.to_be_bytes());
+            buf.extend_from_slice(&(fields.len() as u16).to_be_bytes());
+            for (ie, len) in fields {
+                buf.extend_from_slice(&ie.to_be_bytes());
+                buf.extend_from_slice(&len.to_be_bytes());
+            }
+        }
+        let len = (buf.len() - start) as u16;
+        buf[start + 2..start + 4].copy_from_slice(&len.to_be_bytes());
+    }
+
+    fn data_set(buf: &mut Vec<u8>, template: u16, flows: &[&(FlowKey, FlowStats)], clock: &Clock) -> u32 {
+        if flows.is_empty() {
+            return 0;
+        }
+        let start = buf.len();
+        buf.extend_from_slice(&template.to_be_bytes()); // set id = template id
+        buf.extend_from_slice(&0u16.to_be_bytes());
+        for (key, stats) in flows.iter().map(|f| (&f.0, &f.1)) {
+            match (key.src, key.dst) {
+                (IpAddr::V4(s), IpAddr::V4(d)) => {
+                    buf.extend_from_slice(&s.octets());
+                    buf.extend_from_slice(&d.octets());
+                }
+                (IpAddr::V6(s), IpAddr::V6(d)) => {
+                    buf.extend_from_slice(&s.octets());
+                    buf.extend_from_slice(&d.octets());
+                }
+                _ => unreachable!("a flow never mixes address families"),
+            }
+            buf.extend_from_slice(&key.sport.to_be_bytes());
+            buf.extend_from_slice(&key.dport.to_be_bytes());
+            buf.push(key.proto);
+            buf.push(stats.tcp_flags);
+            buf.extend_from_slice(&key.vlan.unwrap_or(0).to_be_bytes());
+            buf.extend_from_slice(&stats.packets.to_be_bytes());
+            buf.extend_from_slice(&stats.bytes.to_be_bytes());
+            buf.extend_from_slice(&clock.unix_ms(stats.first_seen).to_be_bytes());
+            buf.extend_from_slice(&clock.unix_ms(stats.last_seen).to_be_bytes());
+        }
+        let len = (buf.len() - start) as u16;
+        buf[start + 2..start + 4].copy_from_slice(&len.to_be_bytes());
+        flows.len() as u32
+    }
+
+    fn encode(&mut self, flows: &[&(FlowKey, FlowStats)], clock: &Clock) -> Vec<u8> {
+        let mut buf = Vec::new();
+        // Message header: version(2) length(2) export time(4) sequence(4) domain(4)
+        buf.extend_from_slice(&10u16.to_be_bytes());
+        buf.extend_from_slice(&0u16.to_be_bytes());
+        buf.extend_from_slice(&(clock.unix().as_secs() as u32).to_be_bytes());
+        buf.extend_from_slice(&self.sequence.to_be_bytes());
+        buf.extend_from_slice(&self.domain.to_be_bytes());
+
+        if self.messages % TEMPLATE_EVERY == 0 {
+            Self::template_set(&mut buf);
+        }
+        self.messages += 1;
+
+        let (v4, v6): (Vec<_>, Vec<_>) = flows.iter().copied().partition(|f| f.0.src.is_ipv4());
+        let mut records = Self::data_set(&mut buf, TEMPLATE_V4, &v4, clock);
+        records += Self::data_set(&mut buf, TEMPLATE_V6, &v6, clock);
+
+        let len = buf.len() as u16;
+        buf[2..4].copy_from_slice(&len.to_be_bytes());
+        // IPFIX sequence = number of data records sent before this message
+        self.sequence = self.sequence.wrapping_add(records);
+        buf
+    }
+}
+
+// ---------- time helpers ----------
+
+/// Maps `Instant`s (what the flow table stores) to wall-clock and uptime values.
+struct Clock {
+    start: Instant,
+    start_unix: Duration,
+}
+
+impl Clock {
+    fn new() -> Self {
+        Clock { start: Instant::now(), start_unix: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default() }
+    }
+    fn unix(&self) -> Duration {
+        self.start_unix + self.start.elapsed()
+    }
+    fn unix_ms(&self, t: Instant) -> u64 {
+        (self.start_unix + t.duration_since(self.start)).as_millis() as u64
+    }
+    /// NetFlow v5 uses "milliseconds since the exporter started"
+    fn uptime_ms(&self, t: Instant) -> u32 {
+        t.duration_since(self.start).as_millis() as u32
+    }
+}
+
+// ---------- output ----------
+
+struct Exporter {
+    format: Format,
+    v5: V5Encoder,
+    ipfix: IpfixEncoder,
+    socket: Option<(UdpSocket, String)>,
+    file: Option<File>,
+    clock: Clock,
+    exported: u64,
+}
+
+impl Exporter {
+    fn export(&mut self, flows: &[(FlowKey, FlowStats)]) {
+        let flows: Vec<&(FlowKey, FlowStats)> = match self.format {
+            // NetFlow v5 has no IPv6 fields
+            Format::V5 => flows.iter().filter(|f| f.0.src.is_ipv4()).collect(),
+            Format::Ipfix => flows.iter().collect(),
+        };
+        // Keep every datagram within a 1500-byte MTU. IPFIX records differ in size
+        // (48 bytes for IPv4, 72 for IPv6), so those batches are cut by bytes.
+        let mut start = 0;
+        while start < flows.len() {
+            let end = match self.format {
+                Format::V5 => (start + V5_MAX_RECORDS).min(flows.len()),
+                Format::Ipfix => ipfix_batch_end(&flows, start),
+            };
+            let chunk = &flows[start..end];
+            start = end;
+            let datagram = match self.format {
+                Format::V5 => self.v5.encode(chunk, &self.clock),
+                Format::Ipfix => self.ipfix.encode(chunk, &self.clock),
+            };
+            if let Some((socket, addr)) = &self.socket {
+                if let Err(e) = socket.send_to(&datagram, addr) {
+                    eprintln!("Send to {} failed: {}", addr, e);
+                }
+            }
+            if let Some(file) = &mut self.file {
+                if let Err(e) = file.write_all(&datagram) {
+                    eprintln!("Write failed: {}", e);
+                }
+            }
+            self.exported += chunk.len() as u64;
+        }
+    }
+}
+
+/// Flow key, TCP flags and IP-layer length for one frame, if it is IPv4/IPv6 (optionally 802.1Q-tagged).
+fn flow_of(frame: &[u8]) -> Option<(FlowKey, u8, usize)> {
+    let eth = EthernetPacket::new(frame)?;
+    // One 802.1Q tag. With rx-vlan offload on, the NIC strips it first (see vlan_mpls_decode.rs).
+    let (vlan, ethertype, payload) = match eth.get_ethertype() {
+        EtherTypes::Vlan => {
+            let tag = VlanPacket::new(eth.payload())?;
+            (Some(tag.get_vlan_identifier()), tag.get_ethertype(), &eth.payload()[4..])
+        }
+        other => (None, other, eth.payload()),
+    };
+    // Octet counters are IP-layer bytes (IPFIX octetDeltaCount), not frame bytes.
+    let (src, dst, proto, l4, ip_len): (IpAddr, IpAddr, IpNextHeaderProtocol, Vec<u8>, usize) = match ethertype {
+        EtherTypes::Ipv4 => {
+            let ip = Ipv4Packet::new(payload)?;
+            let len = ip.get_total_length() as usize;
+            (ip.get_source().into(), ip.get_destination().into(), ip.get_next_level_protocol(), ip.payload().to_vec(), len)
+        }
+        EtherTypes::Ipv6 => {
+            let ip = Ipv6Packet::new(payload)?;
+            let len = 40 + ip.get_payload_length() as usize;
+            (ip.get_source().into(), ip.get_destination().into(), ip.get_next_header(), ip.payload().to_vec(), len)
+        }
+        _ => return None,
+    };
+    let (sport, dport, flags) = match proto {
+        IpNextHeaderProtocols::Tcp => TcpPacket::new(&l4).map(|t| (t.get_source(), t.get_destination(), t.get_flags()))?,
+        IpNextHeaderProtocols::Udp => UdpPacket::new(&l4).map(|u| (u.get_source(), u.get_destination(), 0))?,
+        _ => (0, 0, 0),
+    };
+    Some((FlowKey { vlan, src, dst, proto: proto.0, sport, dport }, flags, ip_len))
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    if cli.collector.is_none() && cli.write.is_none() {
+        eprintln!("Please specify --collector <host:port> and/or --write <file>");
+        return;
+    }
+
+    let socket = cli.collector.as_ref().map(|addr| {
+        let socket = UdpSocket::bind("0.0.0.0:0").expect("Failed to open UDP socket");
+        (socket, addr.clone())
+    });
+    let file = cli.write.as_ref().map(|path| {
+        OpenOptions::new().create(true).append(true).open(path).expect("Failed to open output file")
+    });
+
+    let mut exporter = Exporter {
+        format: cli.format,
+        v5: V5Encoder { sequence: 0, engine_id: cli.domain as u8 },
+        ipfix: IpfixEncoder { sequence: 0, domain: cli.domain, messages: 0 },
+        socket,
+        file,
+        clock: Clock::new(),
+        exported: 0,
+    };
+
+    let iface = select::interface_or_exit(&cli.iface);
+
+    let mut config = Config::default();
+    config.read_timeout = Some(Duration::from_secs(1));
+    config.promiscuous = cli.promisc;
+
+    let active = Duration::from_secs(cli.active_timeout);
+    let inactive = Duration::from_secs(cli.inactive_timeout);
+    let mut table = FlowTable::default();
+    let mut last_scan = Instant::now();
+
+    match datalink::channel(&iface, config) {
+        Ok(Channel::Ethernet(_tx, mut rx)) => {
+            println!("Exporting flows from {}", iface.name);
+            loop {
+                match rx.next() {
+                    Ok(frame) => {
+                        if let Some((key, flags, ip_len)) = flow_of(frame) {
+                            table.update(key, ip_len, flags);
+                        }
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                    Err(e) => eprintln!("Receive error: {:?}", e),
+                }
+
+                // Check timeouts once per second, not on every packet.
+                if last_scan.elapsed() >= Duration::from_secs(1) {
+                    let expired = table.expire(Instant::now(), active, inactive);
+                    if !expired.is_empty() {
+                        exporter.export(&expired);
+                        println!("exported {} flows ({} total, {} active)", expired.len(), exporter.exported, table.flows.len());
+                    }
+                    last_scan = Instant::now();
+                }
+            }
+        }
+        Ok(_) => eprintln!("Unsupported channel type on this platform"),
+        Err(e) => eprintln!("Failed to open datalink channel: {}", e),
+    }
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+let idle = now.duration_since(stats.last_seen) >= inactive;
+let long = now.duration_since(stats.first_seen) >= active;
+```
+
+👉 The two standard timeouts. A flow that hits the **active** timeout is exported and removed; if packets keep coming it simply starts again as a new record. The collector adds the pieces up.
+
+---
+
+```rust
+buf.extend_from_slice(&5u16.to_be_bytes()); // version
+...
+buf.extend_from_slice(&clock.uptime_ms(stats.first_seen).to_be_bytes());
+```
+
+👉 **NetFlow v5** is a fixed layout: 24-byte header + 48-byte records, max 30 per datagram, **IPv4 only**. Start/end times are "milliseconds since the exporter booted", so we send `sys_uptime` in the header for the collector to convert.
+
+---
+
+```rust
+(8, 4),   // sourceIPv4Address
+(27, 16), // sourceIPv6Address
+```
+
+👉 **IPFIX** is self-describing. A **template** lists (field id, length) pairs; data records are just the values in that order. We define template **256** for IPv4 and **257** for IPv6 and tag each data set with its template id.
+
+👉 Over UDP a collector that starts later would never see templates sent once, so we repeat them every `TEMPLATE_EVERY` messages.
+
+---
+
+```rust
+let (v4, v6): (Vec<_>, Vec<_>) = flows.iter().copied().partition(|f| f.0.src.is_ipv4());
+```
+
+👉 One IPFIX message can carry both families: one data set per template.
+
+---
+
+```rust
+Format::Ipfix => ipfix_batch_end(&flows, start),
+```
+
+👉 An IPv4 record is 48 bytes, an IPv6 record 72. A fixed "20 records per message" would be 16 + 100 (templates) + 4 + 20 × 72 = **1560 bytes** for IPv6 flows — over the MTU, so the datagram gets fragmented (or dropped). Batches are therefore cut **by bytes**, at `MAX_DATAGRAM` = 1472 (1500 minus IPv4 and UDP headers), assuming the templates are included.
+
+---
+
+```rust
+let len = ip.get_total_length() as usize;
+```
+
+👉 `octetDeltaCount` (and NetFlow's `dOctets`) count **IP-layer** bytes, like a router does: header + payload, no Ethernet header, no padding. `frame.len()` would add 14+ bytes per packet.
+
+👉 `vlanId` is taken from an 802.1Q tag when the frame has one.
+
+---
+
+```rust
+buf[2..4].copy_from_slice(&len.to_be_bytes());
+```
+
+👉 Lengths (message and set) are only known at the end, so we write a placeholder and **patch** it. Same trick for both set headers.
+
+---
+
+# 🔹 Testing With a Local UDP Listener
+
+```sh
+# Terminal 1: a "collector" that just shows datagram sizes
+python3 -c "
+import socket; s=socket.socket(socket.AF_INET, socket.SOCK_DGRAM); s.bind(('127.0.0.1', 2055))
+while True:
+    d, a = s.recvfrom(65535); print(len(d), 'bytes, version', int.from_bytes(d[:2], 'big'))
+"
+
+# Terminal 2
+sudo cargo run -- --iface eth0 --collector 127.0.0.1:2055 --format ipfix --inactive-timeout 5
+```
+
+Output (exporter):
+
+```
+Exporting flows from eth0
+exported 14 flows (14 total, 3 active)
+exported 2 flows (16 total, 4 active)
+```
+
+Output (listener):
+
+```
+844 bytes, version 10
+116 bytes, version 10
+```
+
+👉 The first message carries the templates (16 header + 100 template set) plus 12 IPv4 records (4 + 12 × 48) and 2 IPv6 records (4 + 2 × 72). The second is 16 + 4 + 2 × 48, no templates.
+
+👉 For a real check, point `nfcapd -p 2055` or Wireshark (decode UDP 2055 as CFLOW) at it.
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: feed our existing flow-analysis stack from hosts where only this tool runs.
+* **What**:
+
+  1. `FlowTable` from the VLAN lesson, plus TCP flags and `expire()`.
+  2. Active and inactive timeouts decide when a flow is reported.
+  3. NetFlow v5 (IPv4) or IPFIX with templates for IPv4 and IPv6.
+  4. Send to a UDP collector and/or append to a file.
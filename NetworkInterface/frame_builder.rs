@@ -0,0 +1,640 @@
+# 🔹 Big Picture (Why this program exists)
+
+* The sniffer in `capture_parse_tcp_ip_ethernet.rs` can only be tried **live**: `sudo`, a real interface, and whatever traffic happens to pass by.
+* Unit tests would need frames as bytes. Writing them by hand looks like this:
+
+  ```rust
+  let frame = hex!("ffffffffffff 020000000001 0800 4500002c0001000040067cc5c0a8010a08080808 ...");
+  ```
+
+  One wrong length or checksum and the test checks nonsense.
+
+* This lesson adds two things:
+
+  * a **`FrameBuilder`** that produces valid frames from a readable description, using pnet's `Mutable*Packet` types for every header, with correct lengths and checksums:
+
+    ```rust
+    FrameBuilder::ethernet().ipv4(src, dst).tcp(54321, 80).flags(TcpFlags::SYN).payload(b"hi").build()
+    ```
+
+  * the sniffer's decoding moved out of `main` into **`dissect.rs`**, so it can be called with built frames in **table-driven tests** — no interface, no root.
+
+---
+
+# 🔹 Project Layout
+
+```
+sniffer/
+├── Cargo.toml
+└── src/
+    ├── main.rs            <-- capture loop, now calls dissect::dissect()
+    ├── dissect.rs         <-- Ethernet / IPv4 / TCP dissectors + tests
+    └── frame_builder.rs   <-- FrameBuilder + its own tests (test builds only)
+```
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+```
+
+---
+
+# 🔹 `src/frame_builder.rs`
+
+```rust
+use pnet::packet::ethernet::{EtherType, EtherTypes, MutableEthernetPacket};
+use pnet::packet::icmp::echo_request::MutableEchoRequestPacket;
+use pnet::packet::icmp::{self, IcmpPacket, IcmpTypes};
+use pnet::packet::ip::{IpNextHeaderProtocol, IpNextHeaderProtocols};
+use pnet::packet::ipv4::{self, MutableIpv4Packet};
+use pnet::packet::ipv6::MutableIpv6Packet;
+use pnet::packet::tcp::{self, MutableTcpPacket, TcpFlags};
+use pnet::packet::udp::{self, MutableUdpPacket};
+use pnet::packet::Packet;
+use pnet::util::MacAddr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+#[derive(Clone)]
+enum L3 {
+    V4(Ipv4Addr, Ipv4Addr),
+    V6(Ipv6Addr, Ipv6Addr),
+}
+
+#[derive(Clone)]
+enum L4 {
+    Tcp { sport: u16, dport: u16 },
+    Udp { sport: u16, dport: u16 },
+    IcmpEcho { id: u16, seq: u16 },
+}
+
+/// Builds one Ethernet frame, outermost layer first. Every setter takes and returns `self`,
+/// so a frame reads as one chain; `build()` can be called any number of times.
+#[derive(Clone)]
+pub struct FrameBuilder {
+    src_mac: MacAddr,
+    dst_mac: MacAddr,
+    /// Only used when there is no IP layer (ARP, LLDP, ...).
+    ethertype: EtherType,
+    l3: Option<L3>,
+    ttl: u8,
+    l4: Option<L4>,
+    flags: u8,
+    seq: u32,
+    ack: u32,
+    window: u16,
+    payload: Vec<u8>,
+}
+
+impl FrameBuilder {
+    pub fn ethernet() -> Self {
+        FrameBuilder {
+            src_mac: MacAddr::new(0x02, 0, 0, 0, 0, 0x01),
+            dst_mac: MacAddr::new(0x02, 0, 0, 0, 0, 0x02),
+            ethertype: EtherType(0x88b5), // IEEE "local experimental"
+            l3: None,
+            ttl: 64,
+            l4: None,
+            flags: TcpFlags::ACK,
+            seq: 1,
+            ack: 0,
+            window: 65535,
+            payload: Vec::new(),
+        }
+    }
+
+    pub fn src_mac(mut self, mac: MacAddr) -> Self {
+        self.src_mac = mac;
+        self
+    }
+
+    pub fn dst_mac(mut self, mac: MacAddr) -> Self {
+        self.dst_mac = mac;
+        self
+    }
+
+    /// Ethertype of a frame without IP; the payload becomes the Ethernet payload.
+    pub fn ethertype(mut self, ethertype: EtherType) -> Self {
+        self.ethertype = ethertype;
+        self
+    }
+
+    pub fn ipv4(mut self, src: impl Into<Ipv4Addr>, dst: impl Into<Ipv4Addr>) -> Self {
+        self.l3 = Some(L3::V4(src.into(), dst.into()));
+        self
+    }
+
+    pub fn ipv6(mut self, src: impl Into<Ipv6Addr>, dst: impl Into<Ipv6Addr>) -> Self {
+        self.l3 = Some(L3::V6(src.into(), dst.into()));
+        self
+    }
+
+    /// IPv4 TTL / IPv6 hop limit.
+    pub fn ttl(mut self, ttl: u8) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn tcp(mut self, sport: u16, dport: u16) -> Self {
+        self.l4 = Some(L4::Tcp { sport, dport });
+        self
+    }
+
+    /// TCP flags, e.g. `TcpFlags::SYN | TcpFlags::ACK`. Default: ACK.
+    pub fn flags(mut self, flags: u8) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    pub fn seq(mut self, seq: u32) -> Self {
+        self.seq = seq;
+        self
+    }
+
+    pub fn ack(mut self, ack: u32) -> Self {
+        self.ack = ack;
+        self
+    }
+
+    pub fn window(mut self, window: u16) -> Self {
+        self.window = window;
+        self
+    }
+
+    pub fn udp(mut self, sport: u16, dport: u16) -> Self {
+        self.l4 = Some(L4::Udp { sport, dport });
+        self
+    }
+
+    /// ICMPv4 Echo Request (IPv4 only).
+    pub fn icmp_echo(mut self, id: u16, seq: u16) -> Self {
+        self.l4 = Some(L4::IcmpEcho { id, seq });
+        self
+    }
+
+    pub fn payload(mut self, data: &[u8]) -> Self {
+        self.payload = data.to_vec();
+        self
+    }
+
+    /// The finished frame, padded to the 60-byte Ethernet minimum (no FCS).
+    pub fn build(&self) -> Vec<u8> {
+        let (ethertype, body) = match &self.l3 {
+            Some(L3::V4(src, dst)) => (EtherTypes::Ipv4, self.build_ipv4(*src, *dst)),
+            Some(L3::V6(src, dst)) => (EtherTypes::Ipv6, self.build_ipv6(*src, *dst)),
+            None => (self.ethertype, self.payload.clone()),
+        };
+
+        let len = (MutableEthernetPacket::minimum_packet_size() + body.len()).max(60);
+        let mut buf = vec![0u8; len];
+        let mut eth = MutableEthernetPacket::new(&mut buf).unwrap();
+        eth.set_destination(self.dst_mac);
+        eth.set_source(self.src_mac);
+        eth.set_ethertype(ethertype);
+        eth.set_payload(&body);
+        buf
+    }
+
+    fn build_ipv4(&self, src: Ipv4Addr, dst: Ipv4Addr) -> Vec<u8> {
+        let (proto, body) = self.build_transport(src.into(), dst.into());
+        let total = MutableIpv4Packet::minimum_packet_size() + body.len();
+        assert!(total <= u16::MAX as usize, "IPv4 packet too large: {} bytes", total);
+
+        let mut buf = vec![0u8; total];
+        let mut ip = MutableIpv4Packet::new(&mut buf).unwrap();
+        ip.set_version(4);
+        ip.set_header_length(5);
+        ip.set_total_length(total as u16);
+        ip.set_ttl(self.ttl);
+        ip.set_next_level_protocol(proto);
+        ip.set_source(src);
+        ip.set_destination(dst);
+        ip.set_payload(&body);
+        let sum = ipv4::checksum(&ip.to_immutable());
+        ip.set_checksum(sum);
+        buf
+    }
+
+    fn build_ipv6(&self, src: Ipv6Addr, dst: Ipv6Addr) -> Vec<u8> {
+        let (proto, body) = self.build_transport(src.into(), dst.into());
+        assert!(body.len() <= u16::MAX as usize, "IPv6 payload too large: {} bytes", body.len());
+
+        let mut buf = vec![0u8; MutableIpv6Packet::minimum_packet_size() + body.len()];
+        let mut ip = MutableIpv6Packet::new(&mut buf).unwrap();
+        ip.set_version(6);
+        ip.set_payload_length(body.len() as u16);
+        ip.set_next_header(proto);
+        ip.set_hop_limit(self.ttl);
+        ip.set_source(src);
+        ip.set_destination(dst);
+        ip.set_payload(&body);
+        buf
+    }
+
+    /// Transport header + payload. The TCP/UDP checksum covers a pseudo-header with the IP addresses.
+    fn build_transport(&self, src: IpAddr, dst: IpAddr) -> (IpNextHeaderProtocol, Vec<u8>) {
+        match self.l4 {
+            Some(L4::Tcp { sport, dport }) => {
+                let mut buf = vec![0u8; MutableTcpPacket::minimum_packet_size() + self.payload.len()];
+                let mut tcp = MutableTcpPacket::new(&mut buf).unwrap();
+                tcp.set_source(sport);
+                tcp.set_destination(dport);
+                tcp.set_sequence(self.seq);
+                tcp.set_acknowledgement(self.ack);
+                tcp.set_data_offset(5);
+                tcp.set_flags(self.flags);
+                tcp.set_window(self.window);
+                tcp.set_payload(&self.payload);
+                let sum = match (src, dst) {
+                    (IpAddr::V4(s), IpAddr::V4(d)) => tcp::ipv4_checksum(&tcp.to_immutable(), &s, &d),
+                    (IpAddr::V6(s), IpAddr::V6(d)) => tcp::ipv6_checksum(&tcp.to_immutable(), &s, &d),
+                    _ => unreachable!("src and dst always share a family"),
+                };
+                tcp.set_checksum(sum);
+                (IpNextHeaderProtocols::Tcp, buf)
+            }
+            Some(L4::Udp { sport, dport }) => {
+                let len = MutableUdpPacket::minimum_packet_size() + self.payload.len();
+                let mut buf = vec![0u8; len];
+                let mut udp = MutableUdpPacket::new(&mut buf).unwrap();
+                udp.set_source(sport);
+                udp.set_destination(dport);
+                udp.set_length(len as u16);
+                udp.set_payload(&self.payload);
+                let sum = match (src, dst) {
+                    (IpAddr::V4(s), IpAddr::V4(d)) => udp::ipv4_checksum(&udp.to_immutable(), &s, &d),
+                    (IpAddr::V6(s), IpAddr::V6(d)) => udp::ipv6_checksum(&udp.to_immutable(), &s, &d),
+                    _ => unreachable!("src and dst always share a family"),
+                };
+                udp.set_checksum(sum);
+                (IpNextHeaderProtocols::Udp, buf)
+            }
+            Some(L4::IcmpEcho { id, seq }) => {
+                assert!(src.is_ipv4(), "icmp_echo() needs ipv4()");
+                let mut buf = vec![0u8; MutableEchoRequestPacket::minimum_packet_size() + self.payload.len()];
+                let mut echo = MutableEchoRequestPacket::new(&mut buf).unwrap();
+                echo.set_icmp_type(IcmpTypes::EchoRequest);
+                echo.set_identifier(id);
+                echo.set_sequence_number(seq);
+                echo.set_payload(&self.payload);
+                let sum = icmp::checksum(&IcmpPacket::new(echo.packet()).unwrap());
+                echo.set_checksum(sum);
+                (IpNextHeaderProtocols::Icmp, buf)
+            }
+            // IP with a raw payload: 253 is reserved for experiments (RFC 3692).
+            None => (IpNextHeaderProtocol(253), self.payload.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pnet::packet::ethernet::EthernetPacket;
+    use pnet::packet::ipv4::Ipv4Packet;
+    use pnet::packet::ipv6::Ipv6Packet;
+    use pnet::packet::tcp::TcpPacket;
+    use pnet::packet::udp::UdpPacket;
+
+    const A: [u8; 4] = [192, 168, 1, 10];
+    const B: [u8; 4] = [8, 8, 8, 8];
+
+    #[test]
+    fn frame_lengths() {
+        let cases = [
+            // (name, frame, expected length)
+            ("bare ethernet is padded", FrameBuilder::ethernet().build(), 60),
+            ("ipv4 tcp syn is padded", FrameBuilder::ethernet().ipv4(A, B).tcp(1, 2).build(), 60),
+            ("ipv4 tcp 100 bytes", FrameBuilder::ethernet().ipv4(A, B).tcp(1, 2).payload(&[0; 100]).build(), 14 + 20 + 20 + 100),
+            ("ipv4 udp 30 bytes", FrameBuilder::ethernet().ipv4(A, B).udp(1, 2).payload(&[0; 30]).build(), 14 + 20 + 8 + 30),
+            (
+                "ipv6 tcp",
+                FrameBuilder::ethernet().ipv6(Ipv6Addr::LOCALHOST, Ipv6Addr::LOCALHOST).tcp(1, 2).build(),
+                14 + 40 + 20,
+            ),
+        ];
+        for (name, frame, len) in cases {
+            assert_eq!(frame.len(), len, "{}", name);
+        }
+    }
+
+    #[test]
+    fn ipv4_header_fields_and_checksum() {
+        let frame = FrameBuilder::ethernet().ipv4(A, B).ttl(7).tcp(1, 2).payload(b"abc").build();
+        let eth = EthernetPacket::new(&frame).unwrap();
+        assert_eq!(eth.get_ethertype(), EtherTypes::Ipv4);
+
+        let ip = Ipv4Packet::new(eth.payload()).unwrap();
+        assert_eq!(ip.get_total_length(), 20 + 20 + 3);
+        assert_eq!(ip.get_ttl(), 7);
+        assert_eq!(ip.get_source(), Ipv4Addr::from(A));
+        assert_eq!(ip.get_checksum(), ipv4::checksum(&ip));
+    }
+
+    #[test]
+    fn transport_checksums() {
+        let v6_src: Ipv6Addr = "fd00::1".parse().unwrap();
+        let v6_dst: Ipv6Addr = "fd00::2".parse().unwrap();
+        let cases = [
+            ("tcp v4", FrameBuilder::ethernet().ipv4(A, B).tcp(40000, 443).payload(b"hello")),
+            ("udp v4", FrameBuilder::ethernet().ipv4(A, B).udp(40000, 53).payload(b"hello")),
+            ("tcp v6", FrameBuilder::ethernet().ipv6(v6_src, v6_dst).tcp(40000, 443).payload(b"hello")),
+            ("udp v6", FrameBuilder::ethernet().ipv6(v6_src, v6_dst).udp(40000, 53).payload(b"hello")),
+        ];
+        for (name, builder) in cases {
+            let frame = builder.build();
+            let eth = EthernetPacket::new(&frame).unwrap();
+            // Recompute each checksum from the parsed packet and compare with what was written.
+            let ok = match eth.get_ethertype() {
+                EtherTypes::Ipv4 => {
+                    let ip = Ipv4Packet::new(eth.payload()).unwrap();
+                    let (s, d) = (ip.get_source(), ip.get_destination());
+                    match ip.get_next_level_protocol() {
+                        IpNextHeaderProtocols::Tcp => {
+                            let t = TcpPacket::new(ip.payload()).unwrap();
+                            t.get_checksum() == tcp::ipv4_checksum(&t, &s, &d)
+                        }
+                        _ => {
+                            let u = UdpPacket::new(ip.payload()).unwrap();
+                            u.get_length() as usize == 8 + 5 && u.get_checksum() == udp::ipv4_checksum(&u, &s, &d)
+                        }
+                    }
+                }
+                _ => {
+                    let ip = Ipv6Packet::new(eth.payload()).unwrap();
+                    let (s, d) = (ip.get_source(), ip.get_destination());
+                    match ip.get_next_header() {
+                        IpNextHeaderProtocols::Tcp => {
+                            let t = TcpPacket::new(ip.payload()).unwrap();
+                            t.get_checksum() == tcp::ipv6_checksum(&t, &s, &d)
+                        }
+                        _ => {
+                            let u = UdpPacket::new(ip.payload()).unwrap();
+                            u.get_length() as usize == 8 + 5 && u.get_checksum() == udp::ipv6_checksum(&u, &s, &d)
+                        }
+                    }
+                }
+            };
+            assert!(ok, "{}: bad length or checksum", name);
+        }
+    }
+
+    #[test]
+    fn tcp_flags_and_numbers() {
+        let frame = FrameBuilder::ethernet()
+            .ipv4(A, B)
+            .tcp(54321, 80)
+            .flags(TcpFlags::SYN | TcpFlags::ACK)
+            .seq(1000)
+            .ack(2000)
+            .window(512)
+            .build();
+        let eth = EthernetPacket::new(&frame).unwrap();
+        let ip = Ipv4Packet::new(eth.payload()).unwrap();
+        let t = TcpPacket::new(ip.payload()).unwrap();
+        assert_eq!(t.get_flags(), TcpFlags::SYN | TcpFlags::ACK);
+        assert_eq!((t.get_sequence(), t.get_acknowledgement(), t.get_window()), (1000, 2000, 512));
+        assert_eq!((t.get_source(), t.get_destination()), (54321, 80));
+    }
+}
+```
+
+---
+
+# 🔹 `src/dissect.rs`
+
+The decoding that used to sit inside the capture loop, one function per layer. The output lines are exactly what the sniffer printed before.
+
+```rust
+use pnet::packet::ethernet::{EtherTypes, EthernetPacket};
+use pnet::packet::ip::IpNextHeaderProtocols;
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::tcp::TcpPacket;
+use pnet::packet::Packet;
+
+/// Lines to print for one frame. `None` = not even a valid Ethernet header.
+pub fn dissect(frame: &[u8]) -> Option<Vec<String>> {
+    let eth = EthernetPacket::new(frame)?;
+    let mut out = Vec::new();
+    dissect_ethernet(&eth, &mut out);
+    Some(out)
+}
+
+fn dissect_ethernet(eth: &EthernetPacket, out: &mut Vec<String>) {
+    match eth.get_ethertype() {
+        EtherTypes::Ipv4 => {
+            if let Some(ipv4) = Ipv4Packet::new(eth.payload()) {
+                dissect_ipv4(&ipv4, out);
+            }
+        }
+        EtherTypes::Ipv6 => out.push("IPv6 packet (ignored in this demo)".to_string()),
+        _ => { /* other ethertypes */ }
+    }
+}
+
+fn dissect_ipv4(ipv4: &Ipv4Packet, out: &mut Vec<String>) {
+    out.push(format!("IPv4: {} -> {}", ipv4.get_source(), ipv4.get_destination()));
+    if ipv4.get_next_level_protocol() == IpNextHeaderProtocols::Tcp {
+        if let Some(tcp) = TcpPacket::new(ipv4.payload()) {
+            dissect_tcp(&tcp, out);
+        }
+    }
+}
+
+fn dissect_tcp(tcp: &TcpPacket, out: &mut Vec<String>) {
+    out.push(format!("  TCP: {} -> {}", tcp.get_source(), tcp.get_destination()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_builder::FrameBuilder;
+    use pnet::packet::tcp::TcpFlags;
+    use std::net::Ipv6Addr;
+
+    const CLIENT: [u8; 4] = [192, 168, 1, 10];
+    const SERVER: [u8; 4] = [8, 8, 8, 8];
+
+    /// Cut a built frame short, to fake truncated captures.
+    fn truncated(frame: Vec<u8>, len: usize) -> Vec<u8> {
+        frame[..len].to_vec()
+    }
+
+    #[test]
+    fn dissect_table() {
+        let cases: Vec<(&str, Vec<u8>, Option<Vec<&str>>)> = vec![
+            (
+                "ipv4 tcp syn",
+                FrameBuilder::ethernet().ipv4(CLIENT, SERVER).tcp(54321, 80).flags(TcpFlags::SYN).build(),
+                Some(vec!["IPv4: 192.168.1.10 -> 8.8.8.8", "  TCP: 54321 -> 80"]),
+            ),
+            (
+                "ipv4 tcp reply with payload",
+                FrameBuilder::ethernet()
+                    .ipv4(SERVER, CLIENT)
+                    .tcp(80, 54321)
+                    .flags(TcpFlags::PSH | TcpFlags::ACK)
+                    .payload(b"HTTP/1.1 200 OK\r\n\r\n")
+                    .build(),
+                Some(vec!["IPv4: 8.8.8.8 -> 192.168.1.10", "  TCP: 80 -> 54321"]),
+            ),
+            (
+                "ipv4 udp: address line only",
+                FrameBuilder::ethernet().ipv4(CLIENT, SERVER).udp(5353, 53).payload(&[0; 12]).build(),
+                Some(vec!["IPv4: 192.168.1.10 -> 8.8.8.8"]),
+            ),
+            (
+                "ipv4 icmp: address line only",
+                FrameBuilder::ethernet().ipv4(CLIENT, SERVER).icmp_echo(1, 1).build(),
+                Some(vec!["IPv4: 192.168.1.10 -> 8.8.8.8"]),
+            ),
+            (
+                "ipv6 is reported but not decoded",
+                FrameBuilder::ethernet().ipv6(Ipv6Addr::LOCALHOST, Ipv6Addr::LOCALHOST).tcp(1, 2).build(),
+                Some(vec!["IPv6 packet (ignored in this demo)"]),
+            ),
+            (
+                "arp is skipped",
+                FrameBuilder::ethernet().ethertype(EtherTypes::Arp).payload(&[0; 28]).build(),
+                Some(vec![]),
+            ),
+            (
+                "tcp header cut off: ipv4 line only",
+                truncated(FrameBuilder::ethernet().ipv4(CLIENT, SERVER).tcp(1, 2).payload(&[0; 40]).build(), 14 + 20 + 10),
+                Some(vec!["IPv4: 192.168.1.10 -> 8.8.8.8"]),
+            ),
+            (
+                "ipv4 header cut off: nothing",
+                truncated(FrameBuilder::ethernet().ipv4(CLIENT, SERVER).tcp(1, 2).build(), 14 + 12),
+                Some(vec![]),
+            ),
+            ("shorter than an ethernet header", vec![0u8; 10], None),
+        ];
+
+        for (name, frame, want) in cases {
+            let got = dissect(&frame);
+            let want = want.map(|lines| lines.into_iter().map(String::from).collect::<Vec<_>>());
+            assert_eq!(got, want, "case: {}", name);
+        }
+    }
+
+    #[test]
+    fn ethernet_padding_is_not_tcp_payload() {
+        // A SYN is 54 bytes; build() pads it to 60. The padding must not change the result.
+        let frame = FrameBuilder::ethernet().ipv4(CLIENT, SERVER).tcp(1, 2).flags(TcpFlags::SYN).build();
+        assert_eq!(frame.len(), 60);
+        let eth = EthernetPacket::new(&frame).unwrap();
+        let ip = Ipv4Packet::new(eth.payload()).unwrap();
+        assert_eq!(ip.payload().len(), 20, "Ipv4Packet::payload() stops at total_length");
+        assert_eq!(dissect(&frame).unwrap().len(), 2);
+    }
+}
+```
+
+---
+
+# 🔹 `src/main.rs` (changes only)
+
+```rust
+mod dissect;
+#[cfg(test)]
+mod frame_builder;
+```
+
+and the body of the capture loop shrinks to:
+
+```rust
+                match rx.next() {
+                    Ok(frame) => match dissect::dissect(frame) {
+                        Some(lines) => {
+                            for line in lines {
+                                println!("{}", line);
+                            }
+                        }
+                        None => eprintln!("Malformed ethernet packet"),
+                    },
+                    Err(e) => {
+                        eprintln!("Receive error: {:?}", e);
+                    }
+                }
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+pub fn tcp(mut self, sport: u16, dport: u16) -> Self { self.l4 = Some(L4::Tcp { sport, dport }); self }
+```
+
+👉 Each setter consumes the builder and hands it back, so a frame is one readable chain. Layers are only **recorded** here; nothing is encoded until `build()`.
+
+---
+
+```rust
+let (proto, body) = self.build_transport(src.into(), dst.into());
+...
+ip.set_total_length(total as u16);
+let sum = ipv4::checksum(&ip.to_immutable());
+```
+
+👉 `build()` works **inside out**: payload → TCP/UDP (checksum needs the IP addresses) → IP (length needs the transport size, checksum needs the finished header) → Ethernet. That order is why lengths and checksums always come out right.
+
+---
+
+```rust
+let len = (MutableEthernetPacket::minimum_packet_size() + body.len()).max(60);
+```
+
+👉 Real NICs pad short frames to 60 bytes. The builder does the same, so tests see what a capture would see — including the padding after a 54-byte SYN.
+
+---
+
+```rust
+let cases: Vec<(&str, Vec<u8>, Option<Vec<&str>>)> = vec![ ("ipv4 tcp syn", FrameBuilder::ethernet()..., Some(vec![...])), ... ];
+for (name, frame, want) in cases { assert_eq!(dissect(&frame), want, "case: {}", name); }
+```
+
+👉 **Table-driven**: one row per situation, one loop that checks them all. A new case is one more row; the name tells you which row failed.
+
+---
+
+```rust
+#[cfg(test)]
+mod frame_builder;
+```
+
+👉 The builder is only compiled for `cargo test`, so the sniffer binary doesn't carry it. Remove the `#[cfg(test)]` if you want to use it for injection too (see `packet_inject.rs`).
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: hand-written hex frames are error-prone, and the sniffer could only be tested live.
+* **What**:
+
+  1. `FrameBuilder` – Ethernet / IPv4 / IPv6 / TCP / UDP / ICMP echo with correct lengths, checksums and padding, built on pnet's mutable packets.
+  2. The sniffer's decoding split into `dissect_ethernet`, `dissect_ipv4` and `dissect_tcp`.
+  3. Table-driven tests for those dissectors, including truncated and non-IP frames.
+
+---
+
+👉 Example Run:
+
+```sh
+cargo test
+```
+
+Output:
+
+```
+running 6 tests
+test dissect::tests::dissect_table ... ok
+test dissect::tests::ethernet_padding_is_not_tcp_payload ... ok
+test frame_builder::tests::frame_lengths ... ok
+test frame_builder::tests::ipv4_header_fields_and_checksum ... ok
+test frame_builder::tests::tcp_flags_and_numbers ... ok
+test frame_builder::tests::transport_checksums ... ok
+
+test result: ok. 6 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out
+```
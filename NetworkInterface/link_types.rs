@@ -0,0 +1,370 @@
+# 🔹 Big Picture (Why this program exists)
+
+* The sniffer does `EthernetPacket::new(frame)` on **every** frame. That is only correct when the link really is Ethernet.
+* Other **link types** put something else (or nothing) in front of the IP header:
+
+  | Where you see it | Link type | What's in front of IP |
+  |---|---|---|
+  | Ethernet NIC, veth, bridge, Linux `lo` | Ethernet | 14-byte Ethernet header |
+  | `tun0`, WireGuard `wg0`, PPP, GRE/SIT devices | raw IP | **nothing** |
+  | `tcpdump -i any` files | Linux cooked (SLL) | 16-byte SLL header |
+  | newer `tcpdump -i any` files | Linux cooked v2 (SLL2) | 20-byte SLL2 header |
+  | macOS/BSD loopback captures | NULL / LOOP | 4-byte address family |
+
+* On a VPN tunnel the old sniffer treats the first 14 bytes of the **IP header** as MAC addresses → garbage.
+* Fix:
+
+  1. **Detect** the link type — from `/sys/class/net/<iface>/type` for live capture, from the pcap header for `--read` files.
+  2. Use the **matching link-layer parser** to find the network protocol and where it starts.
+  3. Continue with the usual IPv4/IPv6/TCP decode.
+
+---
+
+# 🔹 Project Setup
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+pcap-file = "2"
+```
+
+👉 The link type is looked up for the *resolved* interface (`src/select.rs`, from `interface_networks.rs`), so `--iface 'tun*'` or `--iface 3` decode exactly like the real name would.
+
+---
+
+# 🔹 Full Code (`src/main.rs`)
+
+```rust
+mod select;
+
+use clap::Parser;
+use pcap_file::pcap::PcapReader;
+use pcap_file::DataLink;
+use pnet::datalink::{self, Channel, Config};
+use pnet::packet::ethernet::{EtherType, EtherTypes, EthernetPacket};
+use pnet::packet::ip::IpNextHeaderProtocols;
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::ipv6::Ipv6Packet;
+use pnet::packet::tcp::TcpPacket;
+use pnet::packet::Packet;
+use std::fs::File;
+use std::time::Duration;
+
+#[derive(Parser)]
+struct Cli {
+    /// Capture live on this interface
+    #[arg(long)]
+    iface: Option<String>,
+
+    /// Read frames from a pcap file instead
+    #[arg(long)]
+    read: Option<String>,
+
+    #[arg(long)]
+    promisc: bool,
+}
+
+/// The link-layer framings we can decode.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum LinkType {
+    Ethernet,
+    /// No link header: the frame starts with the IP header
+    RawIp,
+    /// Linux "cooked" capture, 16-byte header
+    LinuxSll,
+    /// Linux "cooked" capture v2, 20-byte header
+    LinuxSll2,
+    /// BSD loopback: 4-byte address family (host byte order for NULL, big-endian for LOOP)
+    Null { big_endian: bool },
+}
+
+impl LinkType {
+    /// Link type of a live interface, from its ARPHRD_* number in sysfs.
+    fn of_interface(name: &str) -> LinkType {
+        let arphrd = std::fs::read_to_string(format!("/sys/class/net/{}/type", name))
+            .ok()
+            .and_then(|s| s.trim().parse::<u16>().ok())
+            .unwrap_or(1);
+        match arphrd {
+            // ETHER, and LOOPBACK: Linux gives `lo` a (zeroed) Ethernet header
+            1 | 772 => LinkType::Ethernet,
+            // PPP, TUNNEL (ipip), SIT (6in4), IPGRE, NONE (tun, wireguard), RAWIP
+            512 | 768 | 776 | 778 | 65534 | 519 => LinkType::RawIp,
+            _ => LinkType::Ethernet,
+        }
+    }
+
+    /// Link type of a pcap file, from the header's DLT value.
+    fn of_pcap(datalink: DataLink) -> Option<LinkType> {
+        match datalink {
+            DataLink::ETHERNET => Some(LinkType::Ethernet),
+            DataLink::RAW | DataLink::IPV4 | DataLink::IPV6 => Some(LinkType::RawIp),
+            DataLink::LINUX_SLL => Some(LinkType::LinuxSll),
+            DataLink::LINUX_SLL2 => Some(LinkType::LinuxSll2),
+            DataLink::NULL => Some(LinkType::Null { big_endian: false }),
+            DataLink::LOOP => Some(LinkType::Null { big_endian: true }),
+            _ => None,
+        }
+    }
+}
+
+/// Result of the link-layer step: which network protocol follows, where it starts,
+/// and a short text describing the link header.
+struct LinkFrame<'a> {
+    ethertype: EtherType,
+    payload: &'a [u8],
+    info: String,
+}
+
+fn sll_packet_type(t: u16) -> &'static str {
+    match t {
+        0 => "in",
+        1 => "bcast",
+        2 => "mcast",
+        3 => "other-host",
+        4 => "out",
+        _ => "?",
+    }
+}
+
+/// Guess the network protocol from the IP version nibble (raw IP has no type field).
+fn ethertype_from_version(data: &[u8]) -> EtherType {
+    match data.first().map(|b| b >> 4) {
+        Some(4) => EtherTypes::Ipv4,
+        Some(6) => EtherTypes::Ipv6,
+        _ => EtherType(0),
+    }
+}
+
+fn decode_link(link: LinkType, frame: &[u8]) -> Option<LinkFrame<'_>> {
+    match link {
+        LinkType::Ethernet => {
+            let eth = EthernetPacket::new(frame)?;
+            Some(LinkFrame {
+                ethertype: eth.get_ethertype(),
+                payload: &frame[14..],
+                info: format!("ETH {} -> {}", eth.get_source(), eth.get_destination()),
+            })
+        }
+        LinkType::RawIp => Some(LinkFrame {
+            ethertype: ethertype_from_version(frame),
+            payload: frame,
+            info: "RAW".into(),
+        }),
+        LinkType::LinuxSll => {
+            // packet type(2) ARPHRD(2) addr len(2) addr(8) protocol(2)
+            if frame.len() < 16 {
+                return None;
+            }
+            let pkt_type = u16::from_be_bytes([frame[0], frame[1]]);
+            let protocol = u16::from_be_bytes([frame[14], frame[15]]);
+            Some(LinkFrame {
+                ethertype: EtherType(protocol),
+                payload: &frame[16..],
+                info: format!("SLL {}", sll_packet_type(pkt_type)),
+            })
+        }
+        LinkType::LinuxSll2 => {
+            // protocol(2) reserved(2) ifindex(4) ARPHRD(2) packet type(1) addr len(1) addr(8)
+            if frame.len() < 20 {
+                return None;
+            }
+            let protocol = u16::from_be_bytes([frame[0], frame[1]]);
+            let ifindex = u32::from_be_bytes([frame[4], frame[5], frame[6], frame[7]]);
+            Some(LinkFrame {
+                ethertype: EtherType(protocol),
+                payload: &frame[20..],
+                info: format!("SLL2 if={} {}", ifindex, sll_packet_type(frame[10] as u16)),
+            })
+        }
+        LinkType::Null { big_endian } => {
+            // The 4-byte family is AF_INET (2) or one of the AF_INET6 values (24, 28, 30).
+            // Looking at the IP version nibble avoids caring about the byte order.
+            if frame.len() < 4 {
+                return None;
+            }
+            Some(LinkFrame {
+                ethertype: ethertype_from_version(&frame[4..]),
+                payload: &frame[4..],
+                info: if big_endian { "LOOP" } else { "NULL" }.into(),
+            })
+        }
+    }
+}
+
+/// The familiar IPv4/IPv6 + TCP decode, now independent of the link layer.
+fn decode_network(ethertype: EtherType, payload: &[u8]) -> String {
+    match ethertype {
+        EtherTypes::Ipv4 => match Ipv4Packet::new(payload) {
+            Some(ipv4) => {
+                let mut line = format!("IPv4: {} -> {}", ipv4.get_source(), ipv4.get_destination());
+                if ipv4.get_next_level_protocol() == IpNextHeaderProtocols::Tcp {
+                    if let Some(tcp) = TcpPacket::new(ipv4.payload()) {
+                        line += &format!("  TCP: {} -> {}", tcp.get_source(), tcp.get_destination());
+                    }
+                }
+                line
+            }
+            None => "Malformed IPv4 packet".into(),
+        },
+        EtherTypes::Ipv6 => match Ipv6Packet::new(payload) {
+            Some(ipv6) => {
+                let mut line = format!("IPv6: {} -> {}", ipv6.get_source(), ipv6.get_destination());
+                if ipv6.get_next_header() == IpNextHeaderProtocols::Tcp {
+                    if let Some(tcp) = TcpPacket::new(ipv6.payload()) {
+                        line += &format!("  TCP: {} -> {}", tcp.get_source(), tcp.get_destination());
+                    }
+                }
+                line
+            }
+            None => "Malformed IPv6 packet".into(),
+        },
+        other => format!("ethertype {}", other),
+    }
+}
+
+fn print_frame(link: LinkType, frame: &[u8]) {
+    match decode_link(link, frame) {
+        Some(lf) => println!("[{}] {}", lf.info, decode_network(lf.ethertype, lf.payload)),
+        None => eprintln!("Malformed {:?} frame ({} bytes)", link, frame.len()),
+    }
+}
+
+fn read_file(path: &str) {
+    let file = File::open(path).unwrap_or_else(|e| {
+        eprintln!("{}: {}", path, e);
+        std::process::exit(1);
+    });
+    let mut reader = PcapReader::new(file).unwrap_or_else(|e| {
+        eprintln!("{}: not a pcap file: {}", path, e);
+        std::process::exit(1);
+    });
+    let datalink = reader.header().datalink;
+    let link = match LinkType::of_pcap(datalink) {
+        Some(link) => link,
+        None => {
+            eprintln!("{}: unsupported link type {:?}", path, datalink);
+            std::process::exit(1);
+        }
+    };
+    println!("Reading {} (link type {:?})", path, link);
+
+    while let Some(packet) = reader.next_packet() {
+        match packet {
+            Ok(p) => print_frame(link, &p.data),
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                break;
+            }
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    if let Some(path) = &cli.read {
+        read_file(path);
+        return;
+    }
+
+    let iface_name = match cli.iface {
+        Some(n) => n,
+        None => {
+            eprintln!("Please specify --iface <name> or --read <file.pcap>");
+            return;
+        }
+    };
+
+    let iface = select::interface_or_exit(&iface_name);
+    let link = LinkType::of_interface(&iface.name);
+
+    let mut config = Config::default();
+    config.read_timeout = Some(Duration::from_secs(1));
+    config.promiscuous = cli.promisc;
+
+    match datalink::channel(&iface, config) {
+        Ok(Channel::Ethernet(_tx, mut rx)) => {
+            println!("Listening on {} (link type {:?})", iface.name, link);
+            loop {
+                match rx.next() {
+                    Ok(frame) => print_frame(link, frame),
+                    Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                    Err(e) => eprintln!("Receive error: {:?}", e),
+                }
+            }
+        }
+        Ok(_) => eprintln!("Unsupported channel type on this platform"),
+        Err(e) => eprintln!("Failed to open datalink channel: {}", e),
+    }
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+Ok(Channel::Ethernet(_tx, mut rx)) => { ... }
+```
+
+👉 Careful: pnet calls the channel `Ethernet` on **every** Linux interface, even `tun0`. The name describes the API, not what's inside the frames. That's why we can't trust it and look up the link type ourselves.
+
+---
+
+```rust
+std::fs::read_to_string(format!("/sys/class/net/{}/type", name))
+```
+
+👉 The kernel stores each interface's hardware type (`ARPHRD_*`) in sysfs: `1` = Ethernet, `65534` = none (tun/WireGuard), `512` = PPP, `776` = SIT, ... Anything that isn't Ethernet-like sends **raw IP** on a packet socket.
+
+---
+
+```rust
+DataLink::LINUX_SLL => Some(LinkType::LinuxSll),
+```
+
+👉 Every pcap file starts with a header containing a **DLT** number. That tells us the framing of every frame in the file, so we pick the parser once.
+
+---
+
+```rust
+struct LinkFrame<'a> { ethertype: EtherType, payload: &'a [u8], info: String }
+```
+
+👉 All link parsers produce the **same thing**: "protocol X starts here". After that, `decode_network` doesn't care whether the frame came from Ethernet, a VPN or a cooked capture.
+
+👉 Raw IP and NULL have no usable type field, so the IP **version nibble** (first 4 bits: 4 or 6) decides.
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: not every link is Ethernet — tun devices, VPNs and `tcpdump -i any` files were decoded as garbage.
+* **What**:
+
+  1. Detect the link type from sysfs (live) or the pcap header (`--read`).
+  2. Parse Ethernet, raw IP, Linux SLL/SLL2 or NULL/LOOP headers.
+  3. Hand the network protocol + payload to the normal IP/TCP decoder.
+
+---
+
+👉 Example Run:
+
+```sh
+sudo cargo run -- --iface wg0
+sudo tcpdump -i any -w any.pcap   # then:
+cargo run -- --read any.pcap
+```
+
+Output:
+
+```
+Listening on wg0 (link type RawIp)
+[RAW] IPv4: 10.8.0.2 -> 10.8.0.1  TCP: 50412 -> 22
+
+Reading any.pcap (link type LinuxSll2)
+[SLL2 if=2 out] IPv4: 192.168.1.10 -> 140.82.112.4  TCP: 51522 -> 443
+[SLL2 if=1 in] IPv6: ::1 -> ::1  TCP: 8080 -> 40122
+```
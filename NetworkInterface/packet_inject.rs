@@ -0,0 +1,574 @@
+# 🔹 Big Picture (Why this program exists)
+
+* The sniffer opens `Channel::Ethernet(_tx, mut rx)` and **only reads**. The `_tx` half can **send** raw frames too.
+* To test firewall rules (or our own dissectors) we need **known traffic**: "one SYN from 10.0.0.2:4000 to 10.0.0.1:22", "an ARP reply claiming 10.0.0.1", ...
+* This program is the `inject` mode:
+
+  1. Read a **frame specification** from the command line or a file.
+  2. Build Ethernet / ARP / IPv4 / IPv6 / TCP / UDP / ICMP headers with pnet's **mutable packet** types.
+  3. Compute every **checksum** correctly.
+  4. Send the frames on an interface at a chosen **rate**.
+
+---
+
+# 🔹 Spec Format
+
+One frame per spec, layers separated by `/`, fields as `key=value` (scapy-like):
+
+```
+eth(dst=02:00:00:00:00:01)/ipv4(src=10.0.0.2,dst=10.0.0.1,ttl=64)/tcp(sport=4000,dport=22,flags=S)
+eth/ipv6(src=fd00::2,dst=fd00::1)/udp(sport=5353,dport=53)/raw(text=hello)
+eth(dst=ff:ff:ff:ff:ff:ff)/arp(op=reply,psrc=10.0.0.1,pdst=10.0.0.2)
+eth/ipv4(dst=10.0.0.1)/icmp(id=7,seq=1)/raw(hex=deadbeef)
+eth/ipv4(dst=10.0.0.1)/udp(dport=80)/raw(text="GET /index.html, please")
+```
+
+* `eth` – `src` (defaults to the interface MAC), `dst` (defaults to broadcast).
+* `ipv4` – `src`, `dst`, `ttl`, `id`. `ipv6` – `src`, `dst`, `hlim`.
+* `tcp` – `sport`, `dport`, `seq`, `ack`, `win`, `flags` (letters `FSRPAU`). `udp` – `sport`, `dport`.
+* `icmp` (v4) / `icmp6` – echo request, `id`, `seq`.
+* `arp` – `op` (`request`/`reply`), `hwsrc`, `psrc`, `hwdst`, `pdst`.
+* `raw` – payload, `text=...` or `hex=...`. Put the text in double quotes when it contains `,`, `/`, `(` or `)`, or leading/trailing spaces.
+
+In a spec file, empty lines and lines starting with `#` are skipped.
+
+---
+
+# 🔹 Project Setup
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+```
+
+👉 A crafted frame sent out of the wrong interface is worse than no frame: an ambiguous `--iface` is an error listing the candidates (`src/select.rs`, from `interface_networks.rs`), never a guess.
+
+---
+
+# 🔹 Full Code (`src/main.rs`)
+
+```rust
+mod select;
+
+use clap::Parser;
+use pnet::datalink::{self, Channel, Config};
+use pnet::packet::arp::{ArpHardwareTypes, ArpOperations, MutableArpPacket};
+use pnet::packet::ethernet::{EtherType, EtherTypes, MutableEthernetPacket};
+use pnet::packet::icmp::echo_request::MutableEchoRequestPacket;
+use pnet::packet::icmp::{self, IcmpPacket, IcmpTypes};
+use pnet::packet::icmpv6::{self, Icmpv6Packet, Icmpv6Types, MutableIcmpv6Packet};
+use pnet::packet::ip::{IpNextHeaderProtocol, IpNextHeaderProtocols};
+use pnet::packet::ipv4::{self, MutableIpv4Packet};
+use pnet::packet::ipv6::MutableIpv6Packet;
+use pnet::packet::tcp::{self, MutableTcpPacket, TcpFlags};
+use pnet::packet::udp::{self, MutableUdpPacket};
+use pnet::packet::Packet;
+use pnet::util::MacAddr;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[derive(Parser)]
+struct Cli {
+    /// Interface to send on
+    #[arg(long)]
+    iface: String,
+
+    /// Frame specification (repeatable)
+    #[arg(long)]
+    spec: Vec<String>,
+
+    /// File with one frame specification per line
+    #[arg(long)]
+    file: Option<String>,
+
+    /// How many times to send the whole list of frames
+    #[arg(long, default_value_t = 1)]
+    count: u64,
+
+    /// Packets per second (0 = as fast as possible)
+    #[arg(long, default_value_t = 10)]
+    pps: u64,
+
+    /// Print each frame as hex before sending
+    #[arg(long)]
+    dump: bool,
+}
+
+/// One `name(key=value,...)` piece of a spec.
+struct Layer {
+    name: String,
+    fields: HashMap<String, String>,
+}
+
+impl Layer {
+    /// Read a field, or fall back to `default` when it is missing.
+    fn get<T: FromStr>(&self, key: &str, default: T) -> Result<T, String> {
+        match self.fields.get(key) {
+            Some(v) => v
+                .parse()
+                .map_err(|_| format!("{}: bad value for {}: '{}'", self.name, key, v)),
+            None => Ok(default),
+        }
+    }
+
+    /// Read a field that has no sensible default.
+    fn require<T: FromStr>(&self, key: &str) -> Result<T, String> {
+        let v = self
+            .fields
+            .get(key)
+            .ok_or_else(|| format!("{}: missing field '{}'", self.name, key))?;
+        v.parse().map_err(|_| format!("{}: bad value for {}: '{}'", self.name, key, v))
+    }
+}
+
+/// Split on `sep`, except inside parentheses or double quotes, so `raw(text="a/b,c")` stays whole.
+fn split_top(s: &str, sep: char) -> Vec<&str> {
+    let (mut parts, mut depth, mut quoted, mut start) = (Vec::new(), 0usize, false, 0);
+    for (i, c) in s.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth = depth.saturating_sub(1),
+            c if c == sep && !quoted && depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+fn parse_spec(spec: &str) -> Result<Vec<Layer>, String> {
+    if spec.matches('"').count() % 2 != 0 {
+        return Err("unbalanced '\"'".into());
+    }
+    let mut layers = Vec::new();
+    for part in split_top(spec, '/').into_iter().map(str::trim).filter(|p| !p.is_empty()) {
+        let (name, body) = match part.find('(') {
+            Some(open) if part.ends_with(')') => (&part[..open], &part[open + 1..part.len() - 1]),
+            Some(_) => return Err(format!("missing ')' in '{}'", part)),
+            None => (part, ""),
+        };
+        let mut fields = HashMap::new();
+        for kv in split_top(body, ',').into_iter().map(str::trim).filter(|kv| !kv.is_empty()) {
+            let (k, v) = kv
+                .split_once('=')
+                .ok_or_else(|| format!("expected key=value, got '{}'", kv))?;
+            let v = v.trim();
+            let v = v.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(v);
+            fields.insert(k.trim().to_string(), v.to_string());
+        }
+        layers.push(Layer { name: name.trim().to_lowercase(), fields });
+    }
+    Ok(layers)
+}
+
+fn parse_payload(layer: &Layer) -> Result<Vec<u8>, String> {
+    if let Some(text) = layer.fields.get("text") {
+        return Ok(text.as_bytes().to_vec());
+    }
+    if let Some(hex) = layer.fields.get("hex") {
+        // The pairs below are sliced by byte offset, which panics inside a multi-byte character.
+        if !hex.is_ascii() {
+            return Err(format!("raw: bad hex '{}'", hex));
+        }
+        if hex.len() % 2 != 0 {
+            return Err("raw: hex payload needs an even number of digits".into());
+        }
+        return (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| format!("raw: bad hex '{}'", hex)))
+            .collect();
+    }
+    Ok(Vec::new())
+}
+
+fn parse_tcp_flags(s: &str) -> Result<u8, String> {
+    let mut flags = 0;
+    for c in s.chars() {
+        flags |= match c.to_ascii_uppercase() {
+            'F' => TcpFlags::FIN,
+            'S' => TcpFlags::SYN,
+            'R' => TcpFlags::RST,
+            'P' => TcpFlags::PSH,
+            'A' => TcpFlags::ACK,
+            'U' => TcpFlags::URG,
+            _ => return Err(format!("tcp: unknown flag '{}'", c)),
+        };
+    }
+    Ok(flags)
+}
+
+/// Build the transport header + payload. Needs the IP addresses for the checksum.
+fn build_transport(
+    layer: &Layer,
+    payload: &[u8],
+    src: IpAddr,
+    dst: IpAddr,
+) -> Result<(IpNextHeaderProtocol, Vec<u8>), String> {
+    match layer.name.as_str() {
+        "tcp" => {
+            let mut buf = vec![0u8; MutableTcpPacket::minimum_packet_size() + payload.len()];
+            let mut tcp = MutableTcpPacket::new(&mut buf).unwrap();
+            tcp.set_source(layer.get("sport", 40000)?);
+            tcp.set_destination(layer.require("dport")?);
+            tcp.set_sequence(layer.get("seq", 1)?);
+            tcp.set_acknowledgement(layer.get("ack", 0)?);
+            tcp.set_data_offset(5);
+            tcp.set_flags(parse_tcp_flags(&layer.get("flags", "S".to_string())?)?);
+            tcp.set_window(layer.get("win", 65535)?);
+            tcp.set_payload(payload);
+            let sum = match (src, dst) {
+                (IpAddr::V4(s), IpAddr::V4(d)) => tcp::ipv4_checksum(&tcp.to_immutable(), &s, &d),
+                (IpAddr::V6(s), IpAddr::V6(d)) => tcp::ipv6_checksum(&tcp.to_immutable(), &s, &d),
+                _ => unreachable!("src and dst always share a family"),
+            };
+            tcp.set_checksum(sum);
+            Ok((IpNextHeaderProtocols::Tcp, buf))
+        }
+        "udp" => {
+            let len = MutableUdpPacket::minimum_packet_size() + payload.len();
+            let mut buf = vec![0u8; len];
+            let mut udp = MutableUdpPacket::new(&mut buf).unwrap();
+            udp.set_source(layer.get("sport", 40000)?);
+            udp.set_destination(layer.require("dport")?);
+            udp.set_length(len as u16);
+            udp.set_payload(payload);
+            let sum = match (src, dst) {
+                (IpAddr::V4(s), IpAddr::V4(d)) => udp::ipv4_checksum(&udp.to_immutable(), &s, &d),
+                (IpAddr::V6(s), IpAddr::V6(d)) => udp::ipv6_checksum(&udp.to_immutable(), &s, &d),
+                _ => unreachable!("src and dst always share a family"),
+            };
+            udp.set_checksum(sum);
+            Ok((IpNextHeaderProtocols::Udp, buf))
+        }
+        "icmp" => {
+            if !src.is_ipv4() {
+                return Err("icmp needs ipv4, use icmp6 with ipv6".into());
+            }
+            let mut buf = vec![0u8; MutableEchoRequestPacket::minimum_packet_size() + payload.len()];
+            let mut echo = MutableEchoRequestPacket::new(&mut buf).unwrap();
+            echo.set_icmp_type(IcmpTypes::EchoRequest);
+            echo.set_identifier(layer.get("id", 1)?);
+            echo.set_sequence_number(layer.get("seq", 1)?);
+            echo.set_payload(payload);
+            let sum = icmp::checksum(&IcmpPacket::new(echo.packet()).unwrap());
+            echo.set_checksum(sum);
+            Ok((IpNextHeaderProtocols::Icmp, buf))
+        }
+        "icmp6" => {
+            let (s, d) = match (src, dst) {
+                (IpAddr::V6(s), IpAddr::V6(d)) => (s, d),
+                _ => return Err("icmp6 needs ipv6".into()),
+            };
+            // Echo request body = identifier (2) + sequence (2) + data
+            let mut body = Vec::with_capacity(4 + payload.len());
+            body.extend_from_slice(&layer.get::<u16>("id", 1)?.to_be_bytes());
+            body.extend_from_slice(&layer.get::<u16>("seq", 1)?.to_be_bytes());
+            body.extend_from_slice(payload);
+            let mut buf = vec![0u8; MutableIcmpv6Packet::minimum_packet_size() + body.len()];
+            let mut icmp6 = MutableIcmpv6Packet::new(&mut buf).unwrap();
+            icmp6.set_icmpv6_type(Icmpv6Types::EchoRequest);
+            icmp6.set_payload(&body);
+            let sum = icmpv6::checksum(&Icmpv6Packet::new(icmp6.packet()).unwrap(), &s, &d);
+            icmp6.set_checksum(sum);
+            Ok((IpNextHeaderProtocols::Icmpv6, buf))
+        }
+        other => Err(format!("unknown transport layer '{}'", other)),
+    }
+}
+
+fn build_ipv4(layer: &Layer, transport: Option<&Layer>, payload: &[u8]) -> Result<Vec<u8>, String> {
+    let src: Ipv4Addr = layer.get("src", Ipv4Addr::new(10, 0, 0, 2))?;
+    let dst: Ipv4Addr = layer.require("dst")?;
+    let (proto, body) = match transport {
+        Some(t) => build_transport(t, payload, src.into(), dst.into())?,
+        None => (IpNextHeaderProtocol(layer.get("proto", 253)?), payload.to_vec()),
+    };
+
+    let mut buf = vec![0u8; MutableIpv4Packet::minimum_packet_size() + body.len()];
+    let mut ip = MutableIpv4Packet::new(&mut buf).unwrap();
+    ip.set_version(4);
+    ip.set_header_length(5);
+    ip.set_total_length(buf_len(20 + body.len())?);
+    ip.set_identification(layer.get("id", 0)?);
+    ip.set_ttl(layer.get("ttl", 64)?);
+    ip.set_next_level_protocol(proto);
+    ip.set_source(src);
+    ip.set_destination(dst);
+    ip.set_payload(&body);
+    let sum = ipv4::checksum(&ip.to_immutable());
+    ip.set_checksum(sum);
+    Ok(buf)
+}
+
+fn build_ipv6(layer: &Layer, transport: Option<&Layer>, payload: &[u8]) -> Result<Vec<u8>, String> {
+    let src: Ipv6Addr = layer.get("src", "fd00::2".parse().unwrap())?;
+    let dst: Ipv6Addr = layer.require("dst")?;
+    let (proto, body) = match transport {
+        Some(t) => build_transport(t, payload, src.into(), dst.into())?,
+        None => (IpNextHeaderProtocol(layer.get("nh", 59)?), payload.to_vec()),
+    };
+
+    let mut buf = vec![0u8; MutableIpv6Packet::minimum_packet_size() + body.len()];
+    let mut ip = MutableIpv6Packet::new(&mut buf).unwrap();
+    ip.set_version(6);
+    ip.set_payload_length(buf_len(body.len())?);
+    ip.set_next_header(proto);
+    ip.set_hop_limit(layer.get("hlim", 64)?);
+    ip.set_source(src);
+    ip.set_destination(dst);
+    ip.set_payload(&body);
+    Ok(buf)
+}
+
+fn build_arp(layer: &Layer, eth_src: MacAddr) -> Result<Vec<u8>, String> {
+    let mut buf = vec![0u8; MutableArpPacket::minimum_packet_size()];
+    let mut arp = MutableArpPacket::new(&mut buf).unwrap();
+    arp.set_hardware_type(ArpHardwareTypes::Ethernet);
+    arp.set_protocol_type(EtherTypes::Ipv4);
+    arp.set_hw_addr_len(6);
+    arp.set_proto_addr_len(4);
+    arp.set_operation(match layer.get("op", "request".to_string())?.as_str() {
+        "request" => ArpOperations::Request,
+        "reply" => ArpOperations::Reply,
+        other => return Err(format!("arp: unknown op '{}'", other)),
+    });
+    arp.set_sender_hw_addr(layer.get("hwsrc", eth_src)?);
+    arp.set_sender_proto_addr(layer.require("psrc")?);
+    arp.set_target_hw_addr(layer.get("hwdst", MacAddr::zero())?);
+    arp.set_target_proto_addr(layer.require("pdst")?);
+    Ok(buf)
+}
+
+fn buf_len(n: usize) -> Result<u16, String> {
+    u16::try_from(n).map_err(|_| format!("packet too large ({} bytes)", n))
+}
+
+/// Turn a parsed spec into the bytes of one Ethernet frame.
+fn build_frame(layers: &[Layer], iface_mac: MacAddr) -> Result<Vec<u8>, String> {
+    let mut rest = layers;
+
+    // `eth` is optional: without it we still send an Ethernet frame with defaults.
+    let default_eth = Layer { name: "eth".into(), fields: HashMap::new() };
+    let eth = match rest.first() {
+        Some(l) if l.name == "eth" => {
+            rest = &rest[1..];
+            l
+        }
+        _ => &default_eth,
+    };
+    let eth_src: MacAddr = eth.get("src", iface_mac)?;
+    let eth_dst: MacAddr = eth.get("dst", MacAddr::broadcast())?;
+
+    // Optional trailing raw payload.
+    let payload = match rest.last() {
+        Some(l) if l.name == "raw" => {
+            let p = parse_payload(l)?;
+            rest = &rest[..rest.len() - 1];
+            p
+        }
+        _ => Vec::new(),
+    };
+
+    let (ethertype, body): (EtherType, Vec<u8>) = match rest {
+        [] => (EtherType(eth.get("type", 0x88b5)?), payload),
+        [l3, tail @ ..] if tail.len() <= 1 => match l3.name.as_str() {
+            "ipv4" => (EtherTypes::Ipv4, build_ipv4(l3, tail.first(), &payload)?),
+            "ipv6" => (EtherTypes::Ipv6, build_ipv6(l3, tail.first(), &payload)?),
+            "arp" if tail.is_empty() => (EtherTypes::Arp, build_arp(l3, eth_src)?),
+            other => return Err(format!("unexpected layer '{}' after eth", other)),
+        },
+        _ => return Err("too many layers: expected eth/l3/l4/raw".into()),
+    };
+
+    // Pad to the Ethernet minimum (60 bytes without FCS).
+    let len = (MutableEthernetPacket::minimum_packet_size() + body.len()).max(60);
+    let mut buf = vec![0u8; len];
+    let mut frame = MutableEthernetPacket::new(&mut buf).unwrap();
+    frame.set_source(eth_src);
+    frame.set_destination(eth_dst);
+    frame.set_ethertype(ethertype);
+    frame.set_payload(&body);
+    Ok(buf)
+}
+
+fn load_specs(cli: &Cli) -> Result<Vec<String>, String> {
+    let mut specs = cli.spec.clone();
+    if let Some(path) = &cli.file {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        specs.extend(
+            text.lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .map(String::from),
+        );
+    }
+    Ok(specs)
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let iface = select::interface_or_exit(&cli.iface);
+    let iface_mac = iface.mac.unwrap_or_else(MacAddr::zero);
+
+    // Build every frame up front, so a typo fails before anything is sent.
+    let specs = load_specs(&cli).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+    if specs.is_empty() {
+        eprintln!("Nothing to send: use --spec or --file");
+        std::process::exit(1);
+    }
+    let mut frames = Vec::new();
+    for (i, spec) in specs.iter().enumerate() {
+        match parse_spec(spec).and_then(|layers| build_frame(&layers, iface_mac)) {
+            Ok(frame) => frames.push(frame),
+            Err(e) => {
+                eprintln!("spec {} ('{}'): {}", i + 1, spec, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let mut tx = match datalink::channel(&iface, Config::default()) {
+        Ok(Channel::Ethernet(tx, _rx)) => tx,
+        Ok(_) => {
+            eprintln!("Unsupported channel type on this platform");
+            return;
+        }
+        Err(e) => {
+            eprintln!("Failed to open datalink channel: {}", e);
+            return;
+        }
+    };
+
+    let started = Instant::now();
+    let mut sent = 0u64;
+    let mut attempts = 0u64;
+
+    for _ in 0..cli.count {
+        for frame in &frames {
+            if cli.dump {
+                let hex: Vec<String> = frame.iter().map(|b| format!("{:02x}", b)).collect();
+                println!("{}", hex.join(" "));
+            }
+            match tx.send_to(frame, None) {
+                Some(Ok(())) => sent += 1,
+                Some(Err(e)) => eprintln!("Send error: {}", e),
+                None => eprintln!("Send error: no buffer space"),
+            }
+            attempts += 1;
+            // Pace against the start time, so small sleep errors don't add up. Failed sends
+            // take their slot too, otherwise errors would turn into a burst of retries.
+            if cli.pps > 0 {
+                let due = started + Duration::from_secs_f64(attempts as f64 / cli.pps as f64);
+                if let Some(wait) = due.checked_duration_since(Instant::now()) {
+                    std::thread::sleep(wait);
+                }
+            }
+        }
+    }
+
+    let secs = started.elapsed().as_secs_f64().max(0.001);
+    println!("Sent {} frames on {} in {:.2}s ({:.0} pps)", sent, iface.name, secs, sent as f64 / secs);
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+let mut tcp = MutableTcpPacket::new(&mut buf).unwrap();
+tcp.set_source(...); tcp.set_destination(...); tcp.set_flags(...);
+```
+
+👉 The `Mutable*Packet` types are the **write** side of the decoders we used in the sniffer: same fields, but `set_*` instead of `get_*`. They write straight into our `Vec<u8>`.
+
+---
+
+```rust
+tcp::ipv4_checksum(&tcp.to_immutable(), &s, &d)
+```
+
+👉 TCP, UDP and ICMPv6 checksums cover a **pseudo-header** (source + destination IP), so the transport layer is built **inside** the IP builder, once the addresses are known.
+
+👉 IPv4 has its own header checksum (`ipv4::checksum`). IPv6 has **none** — that's why `build_ipv6` never sets one.
+
+---
+
+```rust
+let len = (MutableEthernetPacket::minimum_packet_size() + body.len()).max(60);
+```
+
+👉 Ethernet frames shorter than 60 bytes (without FCS) are invalid; real NICs pad them. We pad with zeros so tiny ARP / ICMP frames look like real ones.
+
+---
+
+```rust
+let due = started + Duration::from_secs_f64(attempts as f64 / cli.pps as f64);
+```
+
+👉 Rate limiting: each frame has a "due time". Sleeping until that time (instead of a fixed `sleep(gap)`) keeps the **average rate exact** even when `sleep` oversleeps a little.
+
+👉 The schedule counts **attempts**, not successes. If it counted `sent`, a failing send wouldn't advance it, and the next frames would go out back to back to "catch up".
+
+👉 `attempts / pps` in floating point, rather than `gap * attempts` with `gap = 1s / pps`: no rounding of the gap to whole nanoseconds, and no `u32` casts, so `--pps` and `--count` can use their whole `u64` range.
+
+👉 We also sleep after the last frame, so `--count 20 --pps 5` takes 20 × 0.2 s = **4.00 s**.
+
+---
+
+```rust
+fn split_top(s: &str, sep: char) -> Vec<&str>
+```
+
+👉 A plain `split('/')` and `split(',')` would cut `raw(text="GET /index.html, please")` apart. `split_top` ignores separators inside parentheses and double quotes, and the quotes are removed from the value.
+
+---
+
+# 🔹 Testing on a veth Pair
+
+```sh
+sudo ip link add veth0 type veth peer name veth1
+sudo ip link set veth0 up && sudo ip link set veth1 up
+
+# Terminal 1: watch with our sniffer
+sudo ./sniffer --iface veth1
+
+# Terminal 2: send 5 SYNs per second, 20 times
+sudo cargo run -- --iface veth0 --pps 5 --count 20 \
+  --spec "eth(dst=ff:ff:ff:ff:ff:ff)/ipv4(src=10.0.0.2,dst=10.0.0.1)/tcp(sport=4000,dport=22,flags=S)"
+```
+
+Output:
+
+```
+Sent 20 frames on veth0 in 4.00s (5 pps)
+```
+
+And in the sniffer:
+
+```
+IPv4: 10.0.0.2 -> 10.0.0.1
+  TCP: 4000 -> 22
+```
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: to test firewalls and dissectors you need traffic you fully control.
+* **What**:
+
+  1. A small text spec describes each frame layer by layer.
+  2. pnet's mutable packets write the headers; checksums are computed for IPv4, TCP, UDP, ICMP and ICMPv6.
+  3. The unused `tx` half of the datalink channel sends them at a fixed rate.
@@ -0,0 +1,435 @@
+# 🔹 Big Picture (Why this program exists)
+
+* A **pcap** file is a recording of frames (what Wireshark/tcpdump save).
+* To reproduce a production incident in a lab we want to **play the recording back** onto an interface, exactly like it happened.
+* This program = `replay` command:
+
+  1. Read frames from a pcap file.
+  2. Send them through the datalink `tx` handle (the half the sniffer never used).
+  3. Choose the **timing**: original gaps, faster/slower, fixed packets per second, or flat out.
+  4. Optionally **rewrite MAC and IP addresses**, so production addresses land on the lab network (checksums are fixed up).
+
+---
+
+# 🔹 Project Setup
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+pcap-file = "2"
+```
+
+👉 `pcap-file` reads and writes the pcap format in pure Rust (no libpcap needed).
+
+👉 Replaying onto the wrong interface floods someone else's network. `src/select.rs` (from `interface_networks.rs`) refuses an `--iface 'veth*'` that matches more than one interface and lists them instead of picking one.
+
+---
+
+# 🔹 Full Code (`src/main.rs`)
+
+```rust
+mod select;
+
+use clap::{Parser, ValueEnum};
+use pcap_file::pcap::PcapReader;
+use pcap_file::DataLink;
+use pnet::datalink::{self, Channel, Config};
+use pnet::ipnetwork::IpNetwork;
+use pnet::packet::ethernet::{EtherTypes, MutableEthernetPacket};
+use pnet::packet::icmpv6::{self, MutableIcmpv6Packet};
+use pnet::packet::ip::{IpNextHeaderProtocol, IpNextHeaderProtocols};
+use pnet::packet::ipv4::{self, MutableIpv4Packet};
+use pnet::packet::ipv6::MutableIpv6Packet;
+use pnet::packet::tcp::{self, MutableTcpPacket};
+use pnet::packet::udp::{self, MutableUdpPacket};
+use pnet::packet::MutablePacket;
+use pnet::util::MacAddr;
+use std::fs::File;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Timing {
+    /// Keep the gaps from the capture
+    Original,
+    /// Original gaps divided by --speed
+    Multiplier,
+    /// Fixed rate of --pps packets per second
+    Pps,
+    /// No waiting at all
+    Fast,
+}
+
+#[derive(Parser)]
+struct Cli {
+    /// pcap file to replay
+    #[arg(long)]
+    read: String,
+
+    /// Interface to send on
+    #[arg(long)]
+    iface: String,
+
+    /// How to pace the packets
+    #[arg(long, value_enum, default_value = "original")]
+    timing: Timing,
+
+    /// Speed factor for --timing multiplier (2.0 = twice as fast)
+    #[arg(long, default_value_t = 1.0)]
+    speed: f64,
+
+    /// Packets per second for --timing pps
+    #[arg(long, default_value_t = 100)]
+    pps: u64,
+
+    /// Replay the file this many times
+    #[arg(long, default_value_t = 1)]
+    loops: u32,
+
+    /// Rewrite a MAC address: OLD=NEW (repeatable)
+    #[arg(long = "rewrite-mac")]
+    rewrite_mac: Vec<String>,
+
+    /// Map a network onto one of the same size or larger, keeping host bits: 10.1.0.0/16=172.16.0.0/16 (repeatable)
+    #[arg(long = "rewrite-ip")]
+    rewrite_ip: Vec<String>,
+}
+
+/// All address rewriting rules.
+struct Rewriter {
+    macs: Vec<(MacAddr, MacAddr)>,
+    nets: Vec<(IpNetwork, IpNetwork)>,
+}
+
+impl Rewriter {
+    fn from_cli(cli: &Cli) -> Result<Self, String> {
+        let mut macs = Vec::new();
+        for rule in &cli.rewrite_mac {
+            let (old, new) = rule.split_once('=').ok_or(format!("bad --rewrite-mac '{}'", rule))?;
+            let old = old.parse().map_err(|_| format!("bad MAC '{}'", old))?;
+            let new = new.parse().map_err(|_| format!("bad MAC '{}'", new))?;
+            macs.push((old, new));
+        }
+        let mut nets = Vec::new();
+        for rule in &cli.rewrite_ip {
+            let (old, new) = rule.split_once('=').ok_or(format!("bad --rewrite-ip '{}'", rule))?;
+            let old: IpNetwork = old.parse().map_err(|e| format!("bad network '{}': {}", old, e))?;
+            let new: IpNetwork = new.parse().map_err(|e| format!("bad network '{}': {}", new, e))?;
+            if old.is_ipv4() != new.is_ipv4() {
+                return Err(format!("'{}': both sides must be the same IP version", rule));
+            }
+            // A smaller target would cut off host bits: 10.1.7.42 and 10.1.8.42 would both become x.x.x.42.
+            if new.prefix() > old.prefix() {
+                return Err(format!("'{}': /{} can't hold every host of /{}", rule, new.prefix(), old.prefix()));
+            }
+            nets.push((old, new));
+        }
+        Ok(Rewriter { macs, nets })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.macs.is_empty() && self.nets.is_empty()
+    }
+
+    fn mac(&self, addr: MacAddr) -> MacAddr {
+        self.macs.iter().find(|(old, _)| *old == addr).map(|(_, new)| *new).unwrap_or(addr)
+    }
+
+    /// new address = new network bits + old host bits
+    fn ip(&self, addr: IpAddr) -> IpAddr {
+        for (old, new) in &self.nets {
+            if !old.contains(addr) {
+                continue;
+            }
+            return match (addr, new) {
+                (IpAddr::V4(a), IpNetwork::V4(n)) => {
+                    let mask = u32::from(n.mask());
+                    IpAddr::V4((u32::from(n.network()) | (u32::from(a) & !mask)).into())
+                }
+                (IpAddr::V6(a), IpNetwork::V6(n)) => {
+                    let mask = u128::from(n.mask());
+                    IpAddr::V6((u128::from(n.network()) | (u128::from(a) & !mask)).into())
+                }
+                _ => addr,
+            };
+        }
+        addr
+    }
+
+    /// Rewrite one Ethernet frame in place. `complete` = the capture was not truncated,
+    /// so the transport checksum can be recomputed over the whole segment.
+    fn apply(&self, frame: &mut [u8], complete: bool) {
+        let mut eth = match MutableEthernetPacket::new(frame) {
+            Some(eth) => eth,
+            None => return,
+        };
+        eth.set_source(self.mac(eth.get_source()));
+        eth.set_destination(self.mac(eth.get_destination()));
+        if self.nets.is_empty() {
+            return;
+        }
+
+        match eth.get_ethertype() {
+            EtherTypes::Ipv4 => {
+                if let Some(mut ip) = MutableIpv4Packet::new(eth.payload_mut()) {
+                    let (src, dst) = match (self.ip(ip.get_source().into()), self.ip(ip.get_destination().into())) {
+                        (IpAddr::V4(s), IpAddr::V4(d)) => (s, d),
+                        _ => return,
+                    };
+                    ip.set_source(src);
+                    ip.set_destination(dst);
+                    ip.set_checksum(ipv4::checksum(&ip.to_immutable()));
+                    // Only the first fragment has a transport header.
+                    if complete && ip.get_fragment_offset() == 0 {
+                        let proto = ip.get_next_level_protocol();
+                        fix_transport(proto, ip.payload_mut(), src.into(), dst.into());
+                    }
+                }
+            }
+            EtherTypes::Ipv6 => {
+                if let Some(mut ip) = MutableIpv6Packet::new(eth.payload_mut()) {
+                    let (src, dst) = match (self.ip(ip.get_source().into()), self.ip(ip.get_destination().into())) {
+                        (IpAddr::V6(s), IpAddr::V6(d)) => (s, d),
+                        _ => return,
+                    };
+                    ip.set_source(src);
+                    ip.set_destination(dst);
+                    if complete {
+                        let proto = ip.get_next_header();
+                        fix_transport(proto, ip.payload_mut(), src.into(), dst.into());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// TCP/UDP/ICMPv6 checksums include the IP addresses, so they must be redone after rewriting.
+fn fix_transport(proto: IpNextHeaderProtocol, segment: &mut [u8], src: IpAddr, dst: IpAddr) {
+    match proto {
+        IpNextHeaderProtocols::Tcp => {
+            if let Some(mut tcp) = MutableTcpPacket::new(segment) {
+                let sum = match (src, dst) {
+                    (IpAddr::V4(s), IpAddr::V4(d)) => tcp::ipv4_checksum(&tcp.to_immutable(), &s, &d),
+                    (IpAddr::V6(s), IpAddr::V6(d)) => tcp::ipv6_checksum(&tcp.to_immutable(), &s, &d),
+                    _ => return,
+                };
+                tcp.set_checksum(sum);
+            }
+        }
+        IpNextHeaderProtocols::Udp => {
+            if let Some(mut udp) = MutableUdpPacket::new(segment) {
+                // UDP over IPv4 may have "no checksum" (0); keep it that way.
+                if src.is_ipv4() && udp.get_checksum() == 0 {
+                    return;
+                }
+                let sum = match (src, dst) {
+                    (IpAddr::V4(s), IpAddr::V4(d)) => udp::ipv4_checksum(&udp.to_immutable(), &s, &d),
+                    (IpAddr::V6(s), IpAddr::V6(d)) => udp::ipv6_checksum(&udp.to_immutable(), &s, &d),
+                    _ => return,
+                };
+                udp.set_checksum(sum);
+            }
+        }
+        // ICMPv6 (unlike ICMPv4) also covers the pseudo-header.
+        IpNextHeaderProtocols::Icmpv6 => {
+            if let (IpAddr::V6(s), IpAddr::V6(d), Some(mut icmp)) = (src, dst, MutableIcmpv6Packet::new(segment)) {
+                icmp.set_checksum(icmpv6::checksum(&icmp.to_immutable(), &s, &d));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// How long after the start of the replay packet number `index` (captured at `ts`) is due.
+fn due_offset(cli: &Cli, index: u64, ts: Duration, first_ts: Duration) -> Duration {
+    let since_first = ts.saturating_sub(first_ts);
+    match cli.timing {
+        Timing::Original => since_first,
+        Timing::Multiplier => since_first.div_f64(cli.speed.max(0.000_001)),
+        Timing::Pps => Duration::from_secs_f64(index as f64 / cli.pps.max(1) as f64),
+        Timing::Fast => Duration::ZERO,
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let rewriter = Rewriter::from_cli(&cli).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+
+    let iface = select::interface_or_exit(&cli.iface);
+
+    let mut tx = match datalink::channel(&iface, Config::default()) {
+        Ok(Channel::Ethernet(tx, _rx)) => tx,
+        Ok(_) => {
+            eprintln!("Unsupported channel type on this platform");
+            return;
+        }
+        Err(e) => {
+            eprintln!("Failed to open datalink channel: {}", e);
+            return;
+        }
+    };
+
+    // Read sysfs once, not once per packet.
+    let max_frame = iface_mtu_frame(&iface.name);
+    let (mut sent, mut skipped, mut errors) = (0u64, 0u64, 0u64);
+    let started = Instant::now();
+
+    for round in 0..cli.loops {
+        let file = File::open(&cli.read).unwrap_or_else(|e| {
+            eprintln!("{}: {}", cli.read, e);
+            std::process::exit(1);
+        });
+        let mut reader = match PcapReader::new(file) {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("{}: not a pcap file: {}", cli.read, e);
+                std::process::exit(1);
+            }
+        };
+        if reader.header().datalink != DataLink::ETHERNET {
+            eprintln!("Only Ethernet captures can be replayed (file has {:?})", reader.header().datalink);
+            std::process::exit(1);
+        }
+
+        let round_start = Instant::now();
+        let mut first_ts = None;
+        let mut index = 0u64;
+
+        while let Some(packet) = reader.next_packet() {
+            let packet = match packet {
+                Ok(p) => p,
+                Err(e) => {
+                    eprintln!("Stopping round {}: {}", round + 1, e);
+                    break;
+                }
+            };
+
+            let first = *first_ts.get_or_insert(packet.timestamp);
+            let due = round_start + due_offset(&cli, index, packet.timestamp, first);
+            if let Some(wait) = due.checked_duration_since(Instant::now()) {
+                std::thread::sleep(wait);
+            }
+            index += 1;
+
+            let mut data = packet.data.into_owned();
+            if data.len() > max_frame {
+                skipped += 1; // e.g. GRO/TSO super-frames captured on the sending host
+                continue;
+            }
+            if !rewriter.is_empty() {
+                rewriter.apply(&mut data, data.len() as u32 == packet.orig_len);
+            }
+
+            match tx.send_to(&data, None) {
+                Some(Ok(())) => sent += 1,
+                _ => errors += 1,
+            }
+        }
+    }
+
+    let secs = started.elapsed().as_secs_f64().max(0.001);
+    println!(
+        "Replayed {} frames on {} in {:.2}s ({:.0} pps), skipped {} oversized, {} send errors",
+        sent, iface.name, secs, sent as f64 / secs, skipped, errors
+    );
+}
+
+/// Largest frame the interface accepts: MTU + 14 bytes Ethernet header + 4 for a VLAN tag.
+fn iface_mtu_frame(iface: &str) -> usize {
+    std::fs::read_to_string(format!("/sys/class/net/{}/mtu", iface))
+        .ok()
+        .and_then(|s| s.trim().parse::<usize>().ok())
+        .unwrap_or(1500)
+        + 18
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+let mut reader = PcapReader::new(file)?;
+while let Some(packet) = reader.next_packet() { ... }
+```
+
+👉 Every pcap record has a **timestamp**, the **captured bytes** and the **original length** (`orig_len`). If the capture used a snap length, `data` can be shorter than the real packet.
+
+---
+
+```rust
+fn due_offset(cli: &Cli, index: u64, ts: Duration, first_ts: Duration) -> Duration
+```
+
+👉 Each packet gets a **due time** measured from the start of the replay:
+
+* `original` → same gap as in the file.
+* `multiplier` → gap ÷ `--speed` (`--speed 10` replays 10× faster, `0.5` at half speed).
+* `pps` → packet *n* is due at *n / pps* seconds.
+* `fast` → never wait.
+
+👉 Sleeping until the due time (not "sleep the gap") means errors don't pile up over a long file.
+
+---
+
+```rust
+IpAddr::V4((u32::from(n.network()) | (u32::from(a) & !mask)).into())
+```
+
+👉 Network mapping keeps the **host part**: with `10.1.0.0/16=172.16.0.0/16`, `10.1.7.42` → `172.16.7.42`. Conversations stay separate after rewriting.
+
+👉 That only works if the new network is **at least as large** as the old one. `10.1.0.0/16=192.168.50.0/24` would keep just 8 host bits, and `10.1.7.42` and `10.1.8.42` would both become `192.168.50.42` — so `from_cli` rejects it.
+
+---
+
+```rust
+ip.set_checksum(ipv4::checksum(&ip.to_immutable()));
+fix_transport(proto, ip.payload_mut(), src.into(), dst.into());
+```
+
+👉 Changing an IP address breaks **two** checksums: the IPv4 header checksum and the TCP/UDP checksum (it covers a pseudo-header with both addresses). We recompute both. IPv6 has no header checksum, but ICMPv6 uses the pseudo-header too, so it is recomputed as well.
+
+👉 If the frame was truncated at capture time (`data.len() != orig_len`) the transport checksum can't be computed — we leave it alone.
+
+---
+
+```rust
+if data.len() > max_frame { skipped += 1; continue; }
+```
+
+👉 Captures taken on a sending host often contain 64 KB "packets" (offloading merged them). The NIC can't send those, so we skip and count them instead of failing. `max_frame` comes from sysfs, read **once** before the loop.
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: reproduce a real incident in an isolated lab without external tools.
+* **What**:
+
+  1. Read a pcap with `pcap-file`.
+  2. Pace it: original, multiplier, fixed pps, or as fast as possible.
+  3. Rewrite MACs and IP networks, fixing IPv4/TCP/UDP/ICMPv6 checksums.
+  4. Send through the datalink `tx` handle.
+
+---
+
+👉 Example Run:
+
+```sh
+sudo cargo run -- --read incident.pcap --iface veth0 --timing multiplier --speed 4 \
+  --rewrite-ip 10.20.0.0/16=192.168.0.0/16 \
+  --rewrite-mac 00:1b:21:aa:bb:cc=02:00:00:00:00:01
+```
+
+Output:
+
+```
+Replayed 18234 frames on veth0 in 41.77s (437 pps), skipped 12 oversized, 0 send errors
+```
@@ -0,0 +1,848 @@
+# 🔹 Big Picture (Why this program exists)
+
+* Customers send us captures under NDA. Before a pcap leaves the team it must be **sanitized**: no real IPs, no real MACs, no application data.
+* Simply replacing every IP with `0.0.0.0` destroys the capture — you can't tell hosts or subnets apart anymore.
+* This `sanitize` command rewrites a pcap file following a **policy file**:
+
+  * **IP addresses** → **prefix-preserving pseudonyms** (Crypto-PAn). Two addresses sharing a /24 still share a /24 after anonymisation, so subnets and routing stay understandable. Keyed: the same key gives the same mapping every time.
+  * **MAC addresses** → keyed random, locally administered MACs.
+  * **Payload** beyond the TCP/UDP/ICMP header → kept, **zeroed**, or **truncated**.
+  * **Checksums** → recomputed, so Wireshark doesn't flag every packet (zeroed when the data they cover was cut).
+  * **Anything it can't parse** → dropped, so an unknown protocol never smuggles a real address through.
+
+---
+
+# 🔹 Project Setup
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+pcap-file = "2"
+aes = "0.8"
+serde = { version = "1", features = ["derive"] }
+toml = "0.8"
+```
+
+Policy file `sanitize.toml`:
+
+```toml
+# 32 random bytes as 64 hex digits: `openssl rand -hex 32`
+key = "6f1c...e2"
+
+ip = "cryptopan"      # cryptopan | keep
+mac = "randomize"     # randomize | keep
+payload = "zero"      # keep | zero | truncate
+
+# Addresses that should stay readable (e.g. public DNS, documentation ranges)
+keep_ips = ["8.8.8.8", "1.1.1.1"]
+```
+
+👉 Keep the key **secret** and **the same** for all captures of one case: then hosts can be correlated across files, but nobody without the key can reverse the mapping.
+
+---
+
+# 🔹 Full Code (`src/main.rs`)
+
+```rust
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::Aes128;
+use clap::Parser;
+use pcap_file::pcap::{PcapPacket, PcapReader, PcapWriter};
+use pcap_file::DataLink;
+use pnet::packet::arp::MutableArpPacket;
+use pnet::packet::ethernet::{EtherType, EtherTypes, MutableEthernetPacket};
+use pnet::packet::icmp::{self, IcmpPacket};
+use pnet::packet::icmpv6::{self, Icmpv6Packet};
+use pnet::packet::ip::{IpNextHeaderProtocol, IpNextHeaderProtocols};
+use pnet::packet::ipv4::{self, Ipv4Flags, MutableIpv4Packet};
+use pnet::packet::ipv6::MutableIpv6Packet;
+use pnet::packet::tcp::{self, MutableTcpPacket};
+use pnet::packet::udp::{self, MutableUdpPacket};
+use pnet::packet::MutablePacket;
+use pnet::util::MacAddr;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs::File;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+#[derive(Parser)]
+struct Cli {
+    /// Input pcap
+    #[arg(long)]
+    read: String,
+    /// Output pcap
+    #[arg(long)]
+    write: String,
+    /// Policy file (TOML)
+    #[arg(long)]
+    policy: String,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum IpMode {
+    Cryptopan,
+    Keep,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum MacMode {
+    Randomize,
+    Keep,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum PayloadMode {
+    Keep,
+    Zero,
+    Truncate,
+}
+
+#[derive(Deserialize)]
+struct Policy {
+    key: String,
+    ip: IpMode,
+    mac: MacMode,
+    payload: PayloadMode,
+    #[serde(default)]
+    keep_ips: Vec<IpAddr>,
+}
+
+/// Crypto-PAn: prefix-preserving IP anonymisation (Xu, Fan, Ammar, Moon).
+/// Bit i of the output depends only on bits 0..i of the input, so shared prefixes stay shared.
+struct CryptoPan {
+    cipher: Aes128,
+    pad: u128,
+}
+
+impl CryptoPan {
+    /// `key` = 32 bytes: first half is the AES key, second half is encrypted to make the pad.
+    fn new(key: &[u8; 32]) -> Self {
+        let cipher = Aes128::new(GenericArray::from_slice(&key[..16]));
+        let mut pad = GenericArray::clone_from_slice(&key[16..]);
+        cipher.encrypt_block(&mut pad);
+        CryptoPan { cipher, pad: u128::from_be_bytes(pad.as_slice().try_into().unwrap()) }
+    }
+
+    /// Anonymise the top `bits` bits of `addr` (left-aligned in a u128).
+    fn anonymize_bits(&self, addr: u128, bits: u32) -> u128 {
+        let mut otp = 0u128;
+        for pos in 0..bits {
+            // First `pos` bits from the address, the rest from the pad.
+            let mask = if pos == 0 { 0 } else { !0u128 << (128 - pos) };
+            let input = (addr & mask) | (self.pad & !mask);
+            let mut block = GenericArray::from(input.to_be_bytes());
+            self.cipher.encrypt_block(&mut block);
+            otp |= ((block[0] >> 7) as u128) << (127 - pos);
+        }
+        addr ^ otp
+    }
+
+    fn ipv4(&self, a: Ipv4Addr) -> Ipv4Addr {
+        let left = (u32::from(a) as u128) << 96;
+        Ipv4Addr::from((self.anonymize_bits(left, 32) >> 96) as u32)
+    }
+
+    fn ipv6(&self, a: Ipv6Addr) -> Ipv6Addr {
+        Ipv6Addr::from(self.anonymize_bits(u128::from(a), 128))
+    }
+
+    /// Keyed MAC pseudonym: stable per MAC, locally administered, never multicast.
+    fn mac(&self, m: MacAddr) -> MacAddr {
+        let mut block = GenericArray::from([0u8; 16]);
+        block[..6].copy_from_slice(&[m.0, m.1, m.2, m.3, m.4, m.5]);
+        block[15] = 0x4d; // domain separation from the IP rounds
+        self.cipher.encrypt_block(&mut block);
+        MacAddr::new((block[0] & 0xfc) | 0x02, block[1], block[2], block[3], block[4], block[5])
+    }
+}
+
+/// Tunnels can nest; past this depth the rest of the packet is dropped instead of rewritten.
+const MAX_DEPTH: u8 = 4;
+
+const ETHERTYPE_VLAN: [u16; 3] = [0x8100, 0x88a8, 0x9100];
+const ETHERTYPE_MPLS: [u16; 2] = [0x8847, 0x8848];
+/// "Transparent Ethernet Bridging": a whole Ethernet frame inside GRE / Geneve.
+const ETHERTYPE_TEB: u16 = 0x6558;
+const VXLAN_PORT: u16 = 4789;
+const GENEVE_PORT: u16 = 6081;
+
+struct Sanitizer {
+    pan: CryptoPan,
+    policy: Policy,
+    keep_ips: HashSet<IpAddr>,
+}
+
+impl Sanitizer {
+    fn ip4(&self, a: Ipv4Addr) -> Ipv4Addr {
+        if self.policy.ip == IpMode::Keep || self.keep_ips.contains(&IpAddr::V4(a)) || a.is_unspecified() || a.is_broadcast() {
+            return a;
+        }
+        self.pan.ipv4(a)
+    }
+
+    fn ip6(&self, a: Ipv6Addr) -> Ipv6Addr {
+        if self.policy.ip == IpMode::Keep || self.keep_ips.contains(&IpAddr::V6(a)) || a.is_unspecified() {
+            return a;
+        }
+        self.pan.ipv6(a)
+    }
+
+    fn mac(&self, m: MacAddr) -> MacAddr {
+        // Broadcast/multicast MACs carry no identity, and changing them breaks the meaning.
+        if self.policy.mac == MacMode::Keep || m.0 & 0x01 == 1 || m == MacAddr::zero() {
+            return m;
+        }
+        self.pan.mac(m)
+    }
+
+    /// Rewrite one Ethernet frame (outer, or inside VXLAN / Geneve / GRE). Returns how many bytes to keep.
+    fn frame(&self, frame: &mut [u8], depth: u8) -> usize {
+        if depth > MAX_DEPTH || frame.len() < 14 {
+            return 0;
+        }
+        if let Some(mut eth) = MutableEthernetPacket::new(frame) {
+            eth.set_source(self.mac(eth.get_source()));
+            eth.set_destination(self.mac(eth.get_destination()));
+        }
+
+        // 802.1Q / QinQ tags: 2-byte TPID (already read as "ethertype") + 2-byte TCI each.
+        let mut off = 12;
+        let mut ethertype = u16::from_be_bytes([frame[12], frame[13]]);
+        while ETHERTYPE_VLAN.contains(&ethertype) {
+            if frame.len() < off + 6 {
+                return off + 2;
+            }
+            off += 4;
+            ethertype = u16::from_be_bytes([frame[off], frame[off + 1]]);
+        }
+        off += 2;
+
+        if ETHERTYPE_MPLS.contains(&ethertype) {
+            // Label stack entries until the bottom-of-stack bit.
+            loop {
+                if frame.len() < off + 4 {
+                    return off;
+                }
+                let bottom = frame[off + 2] & 0x01 != 0;
+                off += 4;
+                if bottom {
+                    break;
+                }
+            }
+            // MPLS has no "next protocol" field: guess from the IP version nibble.
+            ethertype = match frame.get(off).map(|b| b >> 4) {
+                Some(4) => 0x0800,
+                Some(6) => 0x86dd,
+                _ => 0,
+            };
+        }
+        off + self.l3(ethertype, &mut frame[off..], depth)
+    }
+
+    /// Anything we can't parse is dropped: a short packet is better than a leaked address.
+    fn l3(&self, ethertype: u16, data: &mut [u8], depth: u8) -> usize {
+        match EtherType(ethertype) {
+            EtherTypes::Ipv4 => self.ipv4(data, depth),
+            EtherTypes::Ipv6 => self.ipv6(data, depth),
+            EtherTypes::Arp => self.arp(data),
+            _ => 0,
+        }
+    }
+
+    fn arp(&self, data: &mut [u8]) -> usize {
+        match MutableArpPacket::new(data) {
+            Some(mut arp) if arp.get_hw_addr_len() == 6 && arp.get_proto_addr_len() == 4 => {
+                arp.set_sender_hw_addr(self.mac(arp.get_sender_hw_addr()));
+                arp.set_target_hw_addr(self.mac(arp.get_target_hw_addr()));
+                arp.set_sender_proto_addr(self.ip4(arp.get_sender_proto_addr()));
+                arp.set_target_proto_addr(self.ip4(arp.get_target_proto_addr()));
+                MutableArpPacket::minimum_packet_size()
+            }
+            _ => 0,
+        }
+    }
+
+    /// Returns the number of IP-layer bytes to keep. Bytes past the total length
+    /// (Ethernet padding / trailer) are never kept.
+    fn ipv4(&self, data: &mut [u8], depth: u8) -> usize {
+        let len = data.len();
+        if depth > MAX_DEPTH {
+            return 0;
+        }
+        let (hdr, total, proto, src, dst, more, frag_offset) = {
+            let mut ip = match MutableIpv4Packet::new(&mut *data) {
+                Some(ip) => ip,
+                None => return 0,
+            };
+            let hdr = ip.get_header_length() as usize * 4;
+            if hdr < 20 || hdr > len {
+                return 0;
+            }
+            let (src, dst) = (self.ip4(ip.get_source()), self.ip4(ip.get_destination()));
+            ip.set_source(src);
+            ip.set_destination(dst);
+            // Record route / source route / timestamp options hold addresses: overwrite with EOL.
+            ip.packet_mut()[20..hdr].fill(0);
+            ip.set_checksum(ipv4::checksum(&ip.to_immutable()));
+            let more = ip.get_flags() & Ipv4Flags::MoreFragments != 0;
+            (hdr, ip.get_total_length() as usize, ip.get_next_level_protocol(), src, dst, more, ip.get_fragment_offset())
+        };
+
+        // Inside an ICMP error the quoted packet is cut short, so it isn't "complete"
+        // and its transport checksum can't be recomputed.
+        let complete = total <= len && !more;
+        let end = total.clamp(hdr, len);
+        let body = &mut data[hdr..end];
+        if frag_offset != 0 {
+            // Later fragments are all payload.
+            return hdr + self.payload_only(body);
+        }
+        hdr + self.transport(proto, body, src.into(), dst.into(), complete, depth)
+    }
+
+    fn ipv6(&self, data: &mut [u8], depth: u8) -> usize {
+        let len = data.len();
+        if depth > MAX_DEPTH {
+            return 0;
+        }
+        let (src, dst, mut next, plen) = {
+            let mut ip = match MutableIpv6Packet::new(&mut *data) {
+                Some(ip) => ip,
+                None => return 0,
+            };
+            let (src, dst) = (self.ip6(ip.get_source()), self.ip6(ip.get_destination()));
+            ip.set_source(src);
+            ip.set_destination(dst);
+            (src, dst, ip.get_next_header(), ip.get_payload_length() as usize)
+        };
+        let mut complete = 40 + plen <= len;
+        let end = (40 + plen).min(len);
+
+        // Extension headers. Hop-by-hop, destination options and fragment carry no addresses;
+        // a routing header does, so the packet is cut there.
+        let mut off = 40;
+        loop {
+            match next {
+                IpNextHeaderProtocols::Hopopt | IpNextHeaderProtocols::Ipv6Opts => {
+                    if end < off + 8 {
+                        return off;
+                    }
+                    let ext_len = (data[off + 1] as usize + 1) * 8;
+                    if off + ext_len > end {
+                        return off;
+                    }
+                    next = IpNextHeaderProtocol(data[off]);
+                    off += ext_len;
+                }
+                IpNextHeaderProtocols::Ipv6Frag => {
+                    if end < off + 8 {
+                        return off;
+                    }
+                    let frag = u16::from_be_bytes([data[off + 2], data[off + 3]]);
+                    next = IpNextHeaderProtocol(data[off]);
+                    off += 8;
+                    if frag >> 3 != 0 {
+                        return off + self.payload_only(&mut data[off..end]);
+                    }
+                    complete &= frag & 0x1 == 0;
+                }
+                IpNextHeaderProtocols::Ipv6Route => return off,
+                _ => break,
+            }
+        }
+        off + self.transport(next, &mut data[off..end], src.into(), dst.into(), complete, depth)
+    }
+
+    /// Apply the payload policy to data with no header we understand.
+    fn payload_only(&self, data: &mut [u8]) -> usize {
+        match self.policy.payload {
+            PayloadMode::Keep => data.len(),
+            PayloadMode::Zero => {
+                data.iter_mut().for_each(|b| *b = 0);
+                data.len()
+            }
+            PayloadMode::Truncate => 0,
+        }
+    }
+
+    /// Keep `hdr` bytes of header, apply the payload policy to the rest.
+    fn after_header(&self, data: &mut [u8], hdr: usize) -> usize {
+        let hdr = hdr.min(data.len());
+        hdr + self.payload_only(&mut data[hdr..])
+    }
+
+    /// Rewrite the transport layer (and anything tunnelled in it), then fix its checksum.
+    /// Returns the number of transport-layer bytes to keep.
+    fn transport(
+        &self,
+        proto: IpNextHeaderProtocol,
+        data: &mut [u8],
+        src: IpAddr,
+        dst: IpAddr,
+        complete: bool,
+        depth: u8,
+    ) -> usize {
+        let keep = match proto {
+            IpNextHeaderProtocols::Tcp => match MutableTcpPacket::new(&mut *data) {
+                Some(tcp) => {
+                    let hdr = tcp.get_data_offset() as usize * 4;
+                    self.after_header(data, hdr.max(20))
+                }
+                // Quoted in an ICMP error: only ports + sequence number, no checksum.
+                None => return data.len().min(8),
+            },
+            IpNextHeaderProtocols::Udp => {
+                if data.len() < 8 {
+                    return 0;
+                }
+                match udp_tunnel(data) {
+                    Some((inner, ETHERTYPE_TEB)) => inner + self.frame(&mut data[inner..], depth + 1),
+                    Some((inner, ethertype)) => inner + self.l3(ethertype, &mut data[inner..], depth + 1),
+                    None => self.after_header(data, 8),
+                }
+            }
+            IpNextHeaderProtocols::Icmp => self.icmp(data, depth),
+            IpNextHeaderProtocols::Icmpv6 => self.icmpv6(data, depth),
+            // IP-in-IP, 6in4 and GRE: sanitize the inner packet like the outer one.
+            IpNextHeaderProtocols::Ipv4 => return self.ipv4(data, depth + 1),
+            IpNextHeaderProtocols::Ipv6 => return self.ipv6(data, depth + 1),
+            IpNextHeaderProtocols::Gre => return self.gre(data, depth + 1),
+            _ => return self.payload_only(data),
+        };
+
+        // The old checksum was computed over the *real* addresses, so it must never survive:
+        // recompute it when everything it covers is still there, otherwise zero it.
+        if complete && keep == data.len() {
+            fix_checksum(proto, data, src, dst);
+        } else {
+            zero_checksum(proto, data);
+        }
+        keep
+    }
+
+    /// Rewrite the IPv4 address stored at `data[at..at + 4]`.
+    fn ip4_at(&self, data: &mut [u8], at: usize) {
+        let a = Ipv4Addr::new(data[at], data[at + 1], data[at + 2], data[at + 3]);
+        data[at..at + 4].copy_from_slice(&self.ip4(a).octets());
+    }
+
+    /// Rewrite the IPv6 address stored at `data[at..at + 16]`.
+    fn ip6_at(&self, data: &mut [u8], at: usize) {
+        let a: [u8; 16] = data[at..at + 16].try_into().unwrap();
+        data[at..at + 16].copy_from_slice(&self.ip6(Ipv6Addr::from(a)).octets());
+    }
+
+    /// ICMPv4. Error messages quote the offending packet (IP header + at least 8 bytes)
+    /// with the real addresses in it, so the quote is sanitized like any other packet.
+    /// Types we don't know keep only their 8-byte header, whatever the payload policy.
+    fn icmp(&self, data: &mut [u8], depth: u8) -> usize {
+        if data.len() < 8 {
+            return 0;
+        }
+        match data[0] {
+            // Redirect (all codes): the new gateway's address is in the second word.
+            5 => {
+                self.ip4_at(data, 4);
+                8 + self.ipv4(&mut data[8..], depth + 1)
+            }
+            // Destination unreachable, source quench, time exceeded, parameter problem
+            3 | 4 | 11 | 12 => 8 + self.ipv4(&mut data[8..], depth + 1),
+            // Router advertisement: count and entry size (in words), then (address, preference) entries.
+            9 => {
+                let (count, size) = (data[4] as usize, data[5] as usize * 4);
+                let end = 8 + count * size;
+                if size < 8 || end > data.len() {
+                    return 8;
+                }
+                for i in 0..count {
+                    self.ip4_at(data, 8 + i * size);
+                }
+                end
+            }
+            // Echo, router solicitation, timestamp, address mask: no addresses in the body.
+            0 | 8 | 10 | 13 | 14 | 17 | 18 => self.after_header(data, 8),
+            _ => 8,
+        }
+    }
+
+    /// ICMPv6, like `icmp`: quotes, NDP and MLD are rewritten, unknown types keep only the header.
+    fn icmpv6(&self, data: &mut [u8], depth: u8) -> usize {
+        if data.len() < 8 {
+            return 0;
+        }
+        match data[0] {
+            // Destination unreachable, packet too big, time exceeded, parameter problem
+            1..=4 => 8 + self.ipv6(&mut data[8..], depth + 1),
+            // Echo request / reply
+            128 | 129 => self.after_header(data, 8),
+            // MLD query / report / done, MLDv2 report
+            130..=132 | 143 => self.mld(data),
+            // Router / neighbour solicitation + advertisement, redirect
+            133..=137 => self.ndp(data),
+            _ => 8,
+        }
+    }
+
+    /// Multicast Listener Discovery. Group addresses are rewritten too: a solicited-node
+    /// group (ff02::1:ffXX:XXXX) carries the low 24 bits of a unicast address.
+    fn mld(&self, data: &mut [u8]) -> usize {
+        if data[0] != 143 {
+            // MLDv1 (and the fixed part of an MLDv2 query): group address after the header.
+            if data.len() < 24 {
+                return 8;
+            }
+            self.ip6_at(data, 8);
+            if data[0] != 130 || data.len() < 28 {
+                return 24;
+            }
+            // MLDv2 query: number of sources, then the source list.
+            let count = u16::from_be_bytes([data[26], data[27]]) as usize;
+            let end = 28 + 16 * count;
+            if end > data.len() {
+                return 24;
+            }
+            for i in 0..count {
+                self.ip6_at(data, 28 + 16 * i);
+            }
+            return end;
+        }
+
+        // MLDv2 report: records of type, aux length (words), source count, group, sources, aux data.
+        let records = u16::from_be_bytes([data[6], data[7]]) as usize;
+        let mut off = 8;
+        for _ in 0..records {
+            if off + 20 > data.len() {
+                break;
+            }
+            let aux = data[off + 1] as usize * 4;
+            let sources = u16::from_be_bytes([data[off + 2], data[off + 3]]) as usize;
+            let end = off + 20 + 16 * sources + aux;
+            if end > data.len() {
+                break; // malformed: drop the rest
+            }
+            // The group and its sources are back to back.
+            for i in 0..=sources {
+                self.ip6_at(data, off + 4 + 16 * i);
+            }
+            data[end - aux..end].fill(0);
+            off = end;
+        }
+        off
+    }
+
+    /// Neighbour Discovery: target (and redirect destination) addresses in the fixed part,
+    /// MACs and prefixes in the options. Options we don't know are zeroed.
+    fn ndp(&self, data: &mut [u8]) -> usize {
+        let (addrs, opts_at) = match data[0] {
+            133 => (0, 8),
+            134 => (0, 16),
+            135 | 136 => (1, 24),
+            _ => (2, 40),
+        };
+        if data.len() < opts_at {
+            return 8;
+        }
+        for i in 0..addrs {
+            self.ip6_at(data, 8 + i * 16);
+        }
+
+        let mut off = opts_at;
+        while off + 2 <= data.len() {
+            let (kind, len) = (data[off], data[off + 1] as usize * 8);
+            if len == 0 || off + len > data.len() {
+                break; // malformed: drop the rest
+            }
+            let opt = &mut data[off..off + len];
+            match kind {
+                // Source / target link-layer address
+                1 | 2 if len == 8 => {
+                    let m = self.mac(MacAddr::new(opt[2], opt[3], opt[4], opt[5], opt[6], opt[7]));
+                    opt[2..8].copy_from_slice(&[m.0, m.1, m.2, m.3, m.4, m.5]);
+                }
+                // Prefix information: anonymise the prefix, keep host bits zero.
+                3 if len == 32 => {
+                    let bits = opt[2].min(128) as u32;
+                    let prefix: [u8; 16] = opt[16..32].try_into().unwrap();
+                    let anon = u128::from(self.ip6(Ipv6Addr::from(prefix)));
+                    let mask = if bits == 0 { 0 } else { !0u128 << (128 - bits) };
+                    opt[16..32].copy_from_slice(&(anon & mask).to_be_bytes());
+                }
+                // MTU: nothing identifying
+                5 => {}
+                // Redirected header (a quoted packet), RDNSS, DNSSL, ...: wipe the body.
+                _ => opt[2..].fill(0),
+            }
+            off += len;
+        }
+        off
+    }
+
+    /// GRE: 4-byte base header, then optional checksum, key and sequence words.
+    fn gre(&self, data: &mut [u8], depth: u8) -> usize {
+        if data.len() < 4 || depth > MAX_DEPTH {
+            return 0;
+        }
+        let flags = data[0];
+        let hdr = 4 + 4 * [0x80, 0x20, 0x10].iter().filter(|&&f| flags & f != 0).count();
+        if data.len() < hdr {
+            return 0;
+        }
+        let proto = u16::from_be_bytes([data[2], data[3]]);
+        let inner = if proto == ETHERTYPE_TEB {
+            self.frame(&mut data[hdr..], depth)
+        } else {
+            self.l3(proto, &mut data[hdr..], depth)
+        };
+        let keep = hdr + inner;
+        if flags & 0x80 != 0 {
+            // Optional GRE checksum over header + payload: recompute or zero, like TCP/UDP.
+            data[4..6].fill(0);
+            if keep == data.len() {
+                let sum = pnet::util::checksum(data, 2);
+                data[4..6].copy_from_slice(&sum.to_be_bytes());
+            }
+        }
+        keep
+    }
+}
+
+/// VXLAN / Geneve: offset of the inner packet in the UDP datagram and its ethertype.
+fn udp_tunnel(udp: &[u8]) -> Option<(usize, u16)> {
+    let dport = u16::from_be_bytes([udp[2], udp[3]]);
+    match dport {
+        VXLAN_PORT if udp.len() >= 16 => Some((16, ETHERTYPE_TEB)),
+        GENEVE_PORT if udp.len() >= 16 => {
+            let opts = (udp[8] & 0x3f) as usize * 4;
+            let proto = u16::from_be_bytes([udp[10], udp[11]]);
+            (udp.len() >= 16 + opts).then_some((16 + opts, proto))
+        }
+        _ => None,
+    }
+}
+
+fn zero_checksum(proto: IpNextHeaderProtocol, data: &mut [u8]) {
+    let at = match proto {
+        IpNextHeaderProtocols::Tcp => 16,
+        IpNextHeaderProtocols::Udp => 6,
+        IpNextHeaderProtocols::Icmp | IpNextHeaderProtocols::Icmpv6 => 2,
+        _ => return,
+    };
+    if data.len() >= at + 2 {
+        data[at..at + 2].fill(0);
+    }
+}
+
+fn fix_checksum(proto: IpNextHeaderProtocol, data: &mut [u8], src: IpAddr, dst: IpAddr) {
+    match (proto, src, dst) {
+        (IpNextHeaderProtocols::Tcp, IpAddr::V4(s), IpAddr::V4(d)) => {
+            if let Some(mut p) = MutableTcpPacket::new(data) {
+                p.set_checksum(tcp::ipv4_checksum(&p.to_immutable(), &s, &d));
+            }
+        }
+        (IpNextHeaderProtocols::Tcp, IpAddr::V6(s), IpAddr::V6(d)) => {
+            if let Some(mut p) = MutableTcpPacket::new(data) {
+                p.set_checksum(tcp::ipv6_checksum(&p.to_immutable(), &s, &d));
+            }
+        }
+        (IpNextHeaderProtocols::Udp, IpAddr::V4(s), IpAddr::V4(d)) => {
+            if let Some(mut p) = MutableUdpPacket::new(data) {
+                if p.get_checksum() != 0 {
+                    p.set_checksum(udp::ipv4_checksum(&p.to_immutable(), &s, &d));
+                }
+            }
+        }
+        (IpNextHeaderProtocols::Udp, IpAddr::V6(s), IpAddr::V6(d)) => {
+            if let Some(mut p) = MutableUdpPacket::new(data) {
+                p.set_checksum(udp::ipv6_checksum(&p.to_immutable(), &s, &d));
+            }
+        }
+        (IpNextHeaderProtocols::Icmp, _, _) => {
+            if data.len() >= 4 {
+                data[2] = 0;
+                data[3] = 0;
+                let sum = icmp::checksum(&IcmpPacket::new(data).unwrap());
+                data[2..4].copy_from_slice(&sum.to_be_bytes());
+            }
+        }
+        (IpNextHeaderProtocols::Icmpv6, IpAddr::V6(s), IpAddr::V6(d)) => {
+            if data.len() >= 4 {
+                let sum = icmpv6::checksum(&Icmpv6Packet::new(data).unwrap(), &s, &d);
+                data[2..4].copy_from_slice(&sum.to_be_bytes());
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_key(hex: &str) -> Result<[u8; 32], String> {
+    let hex = hex.trim();
+    // Slicing below is by byte offset; a multi-byte character would panic there.
+    if !hex.is_ascii() {
+        return Err("key is not valid hex".into());
+    }
+    if hex.len() != 64 {
+        return Err(format!("key must be 64 hex digits (32 bytes), got {}", hex.len()));
+    }
+    let mut key = [0u8; 32];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| "key is not valid hex".to_string())?;
+    }
+    Ok(key)
+}
+
+fn fail(msg: String) -> ! {
+    eprintln!("{}", msg);
+    std::process::exit(1);
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let policy_text = std::fs::read_to_string(&cli.policy).unwrap_or_else(|e| fail(format!("{}: {}", cli.policy, e)));
+    let policy: Policy = toml::from_str(&policy_text).unwrap_or_else(|e| fail(format!("{}: {}", cli.policy, e)));
+    let key = parse_key(&policy.key).unwrap_or_else(|e| fail(format!("{}: {}", cli.policy, e)));
+    let sanitizer = Sanitizer {
+        pan: CryptoPan::new(&key),
+        keep_ips: policy.keep_ips.iter().copied().collect(),
+        policy,
+    };
+
+    let input = File::open(&cli.read).unwrap_or_else(|e| fail(format!("{}: {}", cli.read, e)));
+    let mut reader = PcapReader::new(input).unwrap_or_else(|e| fail(format!("{}: {}", cli.read, e)));
+    let header = reader.header();
+    if header.datalink != DataLink::ETHERNET {
+        fail(format!("{}: only Ethernet captures are supported (got {:?})", cli.read, header.datalink));
+    }
+
+    let output = File::create(&cli.write).unwrap_or_else(|e| fail(format!("{}: {}", cli.write, e)));
+    let mut writer = PcapWriter::with_header(output, header).unwrap_or_else(|e| fail(format!("{}: {}", cli.write, e)));
+
+    let mut count = 0u64;
+    while let Some(packet) = reader.next_packet() {
+        let packet = packet.unwrap_or_else(|e| fail(format!("{}: {}", cli.read, e)));
+        let mut data = packet.data.into_owned();
+        let keep = sanitizer.frame(&mut data, 0).min(data.len());
+        data.truncate(keep);
+        // orig_len stays the same: readers then show the packet as "captured short".
+        let out = PcapPacket::new(packet.timestamp, packet.orig_len, &data);
+        writer.write_packet(&out).unwrap_or_else(|e| fail(format!("{}: {}", cli.write, e)));
+        count += 1;
+    }
+    println!("Sanitized {} packets: {} -> {}", count, cli.read, cli.write);
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+let mask = if pos == 0 { 0 } else { !0u128 << (128 - pos) };
+let input = (addr & mask) | (self.pad & !mask);
+self.cipher.encrypt_block(&mut block);
+otp |= ((block[0] >> 7) as u128) << (127 - pos);
+```
+
+👉 This is the heart of **Crypto-PAn**. To decide whether to flip bit `pos`, we encrypt **only the bits before it** (padded with a secret pad) and take one bit of the result.
+
+👉 So bit `pos` of the output depends only on bits `0..pos` of the input: `10.1.2.3` and `10.1.2.99` share 24 bits → their pseudonyms share 24 bits too. Subnet structure survives, real addresses don't.
+
+👉 IPv4 uses 32 rounds, IPv6 128 rounds (same algorithm, longer address).
+
+---
+
+```rust
+MacAddr::new((block[0] & 0xfc) | 0x02, block[1], ...)
+```
+
+👉 MACs are encrypted too (stable per key), then marked **locally administered** (`0x02`) and **unicast** (`& 0xfc`), so they can never be mistaken for a real vendor MAC.
+
+---
+
+```rust
+PayloadMode::Zero => data[hdr_len..].iter_mut().for_each(|b| *b = 0),
+PayloadMode::Truncate => hdr_len,
+```
+
+👉 Headers are kept (that's what we debug), application data is not:
+
+* `zero` keeps sizes and timing intact, bytes become `00`.
+* `truncate` drops the bytes; `orig_len` keeps the real size, so Wireshark shows "captured length < frame length".
+
+---
+
+```rust
+fix_checksum(proto, data, src, dst);
+```
+
+👉 New IPs and zeroed payload invalidate every checksum. IPv4 header, TCP, UDP, ICMP and ICMPv6 checksums are recomputed.
+
+👉 After `truncate` (or inside a quoted packet) the data to checksum is gone, so the checksum is set to **0** instead. Keeping the old value would leak a sum over the *real* addresses — with a guessable payload that's enough to test candidate IPs.
+
+---
+
+```rust
+while ETHERTYPE_VLAN.contains(&ethertype) { off += 4; ... }
+if ETHERTYPE_MPLS.contains(&ethertype) { /* labels until bottom-of-stack */ }
+```
+
+👉 802.1Q / QinQ tags and MPLS labels are skipped, then the inner IP packet is sanitized as usual. IP-in-IP, 6in4, GRE, VXLAN and Geneve are followed the same way (up to `MAX_DEPTH` levels).
+
+👉 Everything else at layer 3 (LLDP, unknown ethertypes, a routing header in IPv6, ...) is **dropped**, whatever the payload policy says: the sanitizer can't know where addresses hide in it.
+
+---
+
+```rust
+3 | 4 | 11 | 12 => 8 + self.ipv4(&mut data[8..], depth + 1),
+```
+
+👉 ICMP errors quote the original IP header — with the real addresses. The quote goes through `ipv4()` / `ipv6()` again, so it gets the same pseudonyms as the packet it refers to.
+
+👉 Neighbour Discovery carries addresses outside the IP header too: the **target address**, and **link-layer address options** with real MACs. Both are rewritten; options we don't know are zeroed.
+
+👉 **MLD** (130–132, 143) lists multicast groups and sources; they go through `ip6()` like any address. Solicited-node groups (`ff02::1:ffXX:XXXX`) hide the last 24 bits of a real unicast address, so "it's only multicast" is not a reason to keep them. The ICMPv4 router advertisement's address list is rewritten the same way.
+
+👉 ICMP types not listed (and not known to carry no addresses, like echo or timestamp) keep only their 8-byte header: their body is dropped even with `payload = "keep"`, the same rule as for unknown ethertypes.
+
+👉 Bytes after the IP total length (Ethernet padding / trailer) are never copied to the output.
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: share NDA captures safely without losing their diagnostic value.
+* **What**:
+
+  1. Prefix-preserving, keyed IP pseudonyms (Crypto-PAn) for IPv4 and IPv6, including inside ARP.
+  2. Keyed, locally administered MAC pseudonyms.
+  3. Payload kept, zeroed or truncated after the transport header.
+  4. VLAN/MPLS tags, tunnels, ICMP error quotes, NDP and MLD handled; anything unknown dropped.
+  5. Checksums recomputed (or zeroed); everything driven by `sanitize.toml`.
+
+---
+
+👉 Example Run:
+
+```sh
+cargo run -- --read customer.pcap --write customer-clean.pcap --policy sanitize.toml
+```
+
+Output:
+
+```
+Sanitized 48120 packets: customer.pcap -> customer-clean.pcap
+```
+
+Before / after (same key):
+
+```
+10.20.1.5  -> 10.20.1.77     becomes   118.62.201.41 -> 118.62.201.103
+10.20.9.1                    becomes   118.62.197.12
+```
+
+👉 `10.20.1.5` and `10.20.1.77` share their first **25** bits (last octets `00000101` / `01001101`), and so do their pseudonyms (`00101001` / `01100111`). `10.20.9.1` shares 20 bits with both, before and after.
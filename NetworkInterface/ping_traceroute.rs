@@ -0,0 +1,558 @@
+# 🔹 Big Picture (Why this program exists)
+
+* `ping` and `traceroute` are the first two tools anyone reaches for, and both are just **ICMP**:
+
+  * **ping** sends *Echo Request* and times the *Echo Reply*.
+  * **traceroute** sends the same request with TTL = 1, 2, 3 …; every router that drops it because the TTL ran out answers with *Time Exceeded*, which reveals the path hop by hop.
+
+* There are two ways to open an ICMP socket on Linux:
+
+  * **Raw** (`SOCK_RAW`) – sees every ICMP packet, needs root / `CAP_NET_RAW`.
+  * **Unprivileged "ping socket"** (`SOCK_DGRAM` + `IPPROTO_ICMP`) – allowed for groups listed in `net.ipv4.ping_group_range`. The kernel fills in the identifier and checksum and only gives us *our own* replies.
+
+* This program supports IPv4 and IPv6, count / interval / TTL / size, min/avg/max/mdev statistics, and a TTL-stepped traceroute.
+* The source interface is chosen with **`find_interface_by_name`** from `interface_networks.rs`, so `-I default`, `-I eth*`, `-I 192.168.1.0/24` all work here too.
+
+---
+
+# 🔹 Project Layout
+
+```
+net_probe/
+├── Cargo.toml
+└── src/
+    ├── main.rs     <-- CLI, ping, traceroute
+    ├── icmp.rs     <-- socket setup, echo encoding, reply parsing
+    └── select.rs   <-- copied unchanged from interface_networks.rs
+```
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+socket2 = { version = "0.5", features = ["all"] }
+ctrlc = "3"
+```
+
+---
+
+# 🔹 `src/icmp.rs`
+
+```rust
+use socket2::{Domain, Protocol, SockRef, Socket, Type};
+use std::io;
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+use std::time::Duration;
+
+/// What came back for one of our probes.
+pub enum Reply {
+    /// Echo Reply from the target.
+    Echo { from: IpAddr, seq: u16, ttl: Option<u8> },
+    /// A router dropped the probe because its TTL / hop limit ran out.
+    TimeExceeded { from: IpAddr, seq: u16 },
+    /// Destination / network / port unreachable; `code` says which.
+    Unreachable { from: IpAddr, seq: u16, code: u8 },
+}
+
+impl Reply {
+    pub fn seq(&self) -> u16 {
+        match self {
+            Reply::Echo { seq, .. } | Reply::TimeExceeded { seq, .. } | Reply::Unreachable { seq, .. } => *seq,
+        }
+    }
+
+    pub fn from(&self) -> IpAddr {
+        match self {
+            Reply::Echo { from, .. } | Reply::TimeExceeded { from, .. } | Reply::Unreachable { from, .. } => *from,
+        }
+    }
+}
+
+pub struct IcmpSocket {
+    /// Any datagram socket works with UdpSocket's send_to / recv_from,
+    /// which saves us from socket2's MaybeUninit buffers.
+    sock: UdpSocket,
+    v6: bool,
+    pub raw: bool,
+    ident: u16,
+}
+
+impl IcmpSocket {
+    /// Try an unprivileged ping socket first, then raw. `force_raw` skips the first step.
+    pub fn open(v6: bool, force_raw: bool) -> io::Result<Self> {
+        let (domain, proto) = if v6 { (Domain::IPV6, Protocol::ICMPV6) } else { (Domain::IPV4, Protocol::ICMPV4) };
+        let dgram = if force_raw { None } else { Socket::new(domain, Type::DGRAM, Some(proto)).ok() };
+        let (sock, raw) = match dgram {
+            Some(s) => (s, false),
+            // EACCES: our group isn't in net.ipv4.ping_group_range -> raw (needs CAP_NET_RAW)
+            None => (Socket::new(domain, Type::RAW, Some(proto))?, true),
+        };
+        Ok(IcmpSocket { sock: sock.into(), v6, raw, ident: std::process::id() as u16 })
+    }
+
+    /// Send from `iface` (SO_BINDTODEVICE) and/or from `addr`.
+    pub fn bind(&self, iface: Option<&str>, addr: Option<SocketAddr>) -> io::Result<()> {
+        let s = SockRef::from(&self.sock);
+        if let Some(addr) = addr {
+            s.bind(&addr.into())?;
+        }
+        if let Some(name) = iface {
+            // Needs CAP_NET_RAW; without it the source address alone still picks the route in most setups.
+            if let Err(e) = s.bind_device(Some(name.as_bytes())) {
+                eprintln!("note: cannot bind to device {} ({}), using source address only", name, e);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        let s = SockRef::from(&self.sock);
+        if self.v6 {
+            s.set_unicast_hops_v6(ttl)
+        } else {
+            s.set_ttl(ttl)
+        }
+    }
+
+    /// Echo Request: type, code, checksum, identifier, sequence, then `size` bytes of payload.
+    pub fn send_echo(&self, dst: IpAddr, seq: u16, size: usize) -> io::Result<()> {
+        let mut pkt = vec![0u8; 8 + size];
+        pkt[0] = if self.v6 { 128 } else { 8 };
+        pkt[4..6].copy_from_slice(&self.ident.to_be_bytes());
+        pkt[6..8].copy_from_slice(&seq.to_be_bytes());
+        for (i, b) in pkt[8..].iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        // ICMPv6's checksum covers an IP pseudo-header, so the kernel always computes it.
+        if !self.v6 {
+            let c = pnet::util::checksum(&pkt, 1);
+            pkt[2..4].copy_from_slice(&c.to_be_bytes());
+        }
+        self.sock.send_to(&pkt, SocketAddr::new(dst, 0))?;
+        Ok(())
+    }
+
+    /// Wait up to `timeout` for one ICMP message. `Ok(None)` = timeout or somebody else's packet.
+    pub fn recv(&self, timeout: Duration) -> io::Result<Option<Reply>> {
+        self.sock.set_read_timeout(Some(timeout.max(Duration::from_millis(1))))?;
+        let mut buf = [0u8; 2048];
+        let (n, from) = match self.sock.recv_from(&mut buf) {
+            Ok(r) => r,
+            Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let mut data = &buf[..n];
+        let mut ttl = None;
+        if self.raw && !self.v6 {
+            // Raw IPv4 sockets deliver the IP header as well; IPv6 raw and ping sockets don't.
+            let ihl = (data[0] & 0x0f) as usize * 4;
+            ttl = Some(data[8]);
+            data = &data[ihl.min(n)..];
+        }
+        Ok(self.parse(from.ip(), data, ttl))
+    }
+
+    fn parse(&self, from: IpAddr, icmp: &[u8], ttl: Option<u8>) -> Option<Reply> {
+        if icmp.len() < 8 {
+            return None;
+        }
+        let (echo_reply, time_exceeded, unreachable) = if self.v6 { (129, 3, 1) } else { (0, 11, 3) };
+        let id_seq = |b: &[u8]| (u16::from_be_bytes([b[4], b[5]]), u16::from_be_bytes([b[6], b[7]]));
+
+        let kind = icmp[0];
+        if kind == echo_reply {
+            let (id, seq) = id_seq(icmp);
+            return self.ours(id).then_some(Reply::Echo { from, seq, ttl });
+        }
+        if kind == time_exceeded || kind == unreachable {
+            // Errors quote the original IP header + the first 8 bytes of our Echo Request.
+            let inner = &icmp[8..];
+            let hdr = if self.v6 { 40 } else { (inner.first()? & 0x0f) as usize * 4 };
+            let (id, seq) = id_seq(inner.get(hdr..hdr + 8)?);
+            if !self.ours(id) {
+                return None;
+            }
+            return Some(if kind == time_exceeded {
+                Reply::TimeExceeded { from, seq }
+            } else {
+                Reply::Unreachable { from, seq, code: icmp[1] }
+            });
+        }
+        None
+    }
+
+    /// Raw sockets see every ICMP packet on the host; ping sockets are already demultiplexed.
+    fn ours(&self, id: u16) -> bool {
+        !self.raw || id == self.ident
+    }
+}
+```
+
+---
+
+# 🔹 `src/main.rs`
+
+```rust
+mod icmp;
+mod select;
+
+use clap::{Args, Parser, Subcommand};
+use icmp::{IcmpSocket, Reply};
+use pnet::datalink::NetworkInterface;
+use std::net::{IpAddr, SocketAddr, SocketAddrV6, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    cmd: Cmd,
+}
+
+#[derive(Args)]
+struct Common {
+    /// Host name or address
+    target: String,
+    /// Source interface (name, index, address, glob, `default` ...)
+    #[arg(short = 'I', long)]
+    iface: Option<String>,
+    /// Use IPv6
+    #[arg(short = '6', long)]
+    ipv6: bool,
+    /// Payload bytes after the 8-byte ICMP header
+    #[arg(short, long, default_value_t = 56)]
+    size: usize,
+    /// Seconds to wait for each reply
+    #[arg(short = 'W', long, default_value_t = 1.0)]
+    wait: f64,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    Ping {
+        #[command(flatten)]
+        common: Common,
+        /// Number of requests (0 = until Ctrl+C)
+        #[arg(short, long, default_value_t = 4)]
+        count: u32,
+        /// Seconds between requests
+        #[arg(short, long, default_value_t = 1.0)]
+        interval: f64,
+        /// IP TTL / IPv6 hop limit
+        #[arg(short, long)]
+        ttl: Option<u32>,
+        /// Always use a raw socket
+        #[arg(long)]
+        raw: bool,
+    },
+    Traceroute {
+        #[command(flatten)]
+        common: Common,
+        #[arg(short, long, default_value_t = 30)]
+        max_hops: u32,
+        /// Probes per hop
+        #[arg(short = 'q', long, default_value_t = 3)]
+        probes: u32,
+    },
+}
+
+fn resolve(target: &str, v6: bool) -> Result<IpAddr, String> {
+    if let Ok(ip) = target.parse::<IpAddr>() {
+        return Ok(ip);
+    }
+    (target, 0)
+        .to_socket_addrs()
+        .map_err(|e| format!("{}: {}", target, e))?
+        .map(|a| a.ip())
+        .find(|ip| ip.is_ipv6() == v6)
+        .ok_or_else(|| format!("{}: no {} address", target, if v6 { "IPv6" } else { "IPv4" }))
+}
+
+/// An address of `iface` in the right family. For IPv6 a global address is preferred; a
+/// link-local fe80:: one only works with a scope id, so it gets the interface index.
+fn source(iface: &NetworkInterface, v6: bool) -> Option<SocketAddr> {
+    let link_local = |ip: &IpAddr| matches!(ip, IpAddr::V6(a) if a.segments()[0] & 0xffc0 == 0xfe80);
+    let ips: Vec<IpAddr> = iface.ips.iter().map(|n| n.ip()).filter(|ip| ip.is_ipv6() == v6).collect();
+    let ip = ips.iter().find(|ip| !link_local(ip)).or(ips.first())?;
+    Some(match ip {
+        IpAddr::V6(a) if link_local(ip) => SocketAddrV6::new(*a, 0, 0, iface.index).into(),
+        _ => SocketAddr::new(*ip, 0),
+    })
+}
+
+/// Open the socket and bind it to the chosen interface and one of its addresses.
+fn open(common: &Common, dst: IpAddr, force_raw: bool) -> Result<IcmpSocket, String> {
+    let sock = IcmpSocket::open(dst.is_ipv6(), force_raw).map_err(|e| {
+        format!("cannot open ICMP socket: {} (run as root, setcap cap_net_raw+ep, or widen net.ipv4.ping_group_range)", e)
+    })?;
+    if let Some(q) = &common.iface {
+        let iface = select::find_interface_by_name(q).map_err(|e| e.to_string())?;
+        let src = source(&iface, dst.is_ipv6());
+        sock.bind(Some(&iface.name), src).map_err(|e| format!("bind {}: {}", iface.name, e))?;
+    }
+    Ok(sock)
+}
+
+/// Sleep for `gap`, waking early once `stop` is set.
+fn pause(gap: Duration, stop: &AtomicBool) {
+    let until = Instant::now() + gap;
+    while !stop.load(Ordering::Relaxed) {
+        match until.checked_duration_since(Instant::now()) {
+            Some(left) => thread::sleep(left.min(Duration::from_millis(100))),
+            None => break,
+        }
+    }
+}
+
+/// Wait until the reply for `seq` arrives or `timeout` passes; returns it with its RTT.
+fn wait_for(sock: &IcmpSocket, seq: u16, sent: Instant, timeout: Duration) -> Option<(Reply, Duration)> {
+    let deadline = sent + timeout;
+    while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+        match sock.recv(left) {
+            Ok(Some(r)) if r.seq() == seq => return Some((r, sent.elapsed())),
+            Ok(_) => {} // late reply for an older seq, or not ours
+            Err(e) => {
+                eprintln!("recv: {}", e);
+                return None;
+            }
+        }
+    }
+    None
+}
+
+fn ping(common: Common, count: u32, interval: f64, ttl: Option<u32>, raw: bool) -> Result<(), String> {
+    let dst = resolve(&common.target, common.ipv6)?;
+    let sock = open(&common, dst, raw)?;
+    if let Some(ttl) = ttl {
+        sock.set_ttl(ttl).map_err(|e| e.to_string())?;
+    }
+    println!("PING {} ({}) {} data bytes{}", common.target, dst, common.size, if sock.raw { " [raw]" } else { "" });
+
+    // Ctrl+C ends the loop instead of the process, so the statistics still get printed.
+    let stop = Arc::new(AtomicBool::new(false));
+    let flag = stop.clone();
+    ctrlc::set_handler(move || flag.store(true, Ordering::Relaxed)).map_err(|e| e.to_string())?;
+
+    let wait = Duration::from_secs_f64(common.wait);
+    let mut rtts: Vec<f64> = Vec::new();
+    let mut sent = 0u32;
+    let mut seq = 0u16;
+    while (count == 0 || sent < count) && !stop.load(Ordering::Relaxed) {
+        seq = seq.wrapping_add(1);
+        let start = Instant::now();
+        sock.send_echo(dst, seq, common.size).map_err(|e| format!("send: {}", e))?;
+        sent += 1;
+
+        match wait_for(&sock, seq, start, wait) {
+            Some((Reply::Echo { from, ttl, .. }, rtt)) => {
+                let ms = rtt.as_secs_f64() * 1000.0;
+                rtts.push(ms);
+                let ttl = ttl.map(|t| format!(" ttl={}", t)).unwrap_or_default();
+                println!("{} bytes from {}: icmp_seq={}{} time={:.3} ms", 8 + common.size, from, seq, ttl, ms);
+            }
+            Some((Reply::TimeExceeded { from, .. }, _)) => println!("From {} icmp_seq={} Time to live exceeded", from, seq),
+            Some((Reply::Unreachable { from, code, .. }, _)) => {
+                println!("From {} icmp_seq={} Destination unreachable (code {})", from, seq, code)
+            }
+            None if stop.load(Ordering::Relaxed) => {} // interrupted while waiting, not a timeout
+            None => println!("Request timeout for icmp_seq={}", seq),
+        }
+
+        if count == 0 || sent < count {
+            let gap = Duration::from_secs_f64(interval);
+            pause(gap.saturating_sub(start.elapsed()), &stop);
+        }
+    }
+
+    let received = rtts.len() as u32;
+    println!("\n--- {} ping statistics ---", common.target);
+    println!(
+        "{} packets transmitted, {} received, {:.1}% packet loss",
+        sent,
+        received,
+        100.0 * (sent - received) as f64 / sent.max(1) as f64
+    );
+    if received > 0 {
+        let n = rtts.len() as f64;
+        let min = rtts.iter().cloned().fold(f64::MAX, f64::min);
+        let max = rtts.iter().cloned().fold(0.0, f64::max);
+        let avg = rtts.iter().sum::<f64>() / n;
+        // Same "mdev" as iputils: sqrt(mean(x²) - mean(x)²)
+        let mdev = (rtts.iter().map(|x| x * x).sum::<f64>() / n - avg * avg).max(0.0).sqrt();
+        println!("rtt min/avg/max/mdev = {:.3}/{:.3}/{:.3}/{:.3} ms", min, avg, max, mdev);
+    }
+    Ok(())
+}
+
+fn traceroute(common: Common, max_hops: u32, probes: u32) -> Result<(), String> {
+    let dst = resolve(&common.target, common.ipv6)?;
+    // Ping sockets don't receive Time Exceeded (it goes to the error queue), so traceroute needs raw.
+    let sock = open(&common, dst, true)?;
+    println!("traceroute to {} ({}), {} hops max, {} byte packets", common.target, dst, max_hops, 8 + common.size);
+
+    let wait = Duration::from_secs_f64(common.wait);
+    let mut seq = 0u16;
+    for ttl in 1..=max_hops {
+        sock.set_ttl(ttl).map_err(|e| e.to_string())?;
+        print!("{:>2} ", ttl);
+        let mut last_from: Option<IpAddr> = None;
+        let mut done = false;
+
+        for _ in 0..probes {
+            seq = seq.wrapping_add(1);
+            let start = Instant::now();
+            sock.send_echo(dst, seq, common.size).map_err(|e| format!("send: {}", e))?;
+            match wait_for(&sock, seq, start, wait) {
+                Some((reply, rtt)) => {
+                    // Print the address again only if this probe took a different path.
+                    if last_from != Some(reply.from()) {
+                        print!(" {}", reply.from());
+                        last_from = Some(reply.from());
+                    }
+                    print!("  {:.3} ms", rtt.as_secs_f64() * 1000.0);
+                    match reply {
+                        Reply::Echo { .. } => done = true,
+                        Reply::Unreachable { code, .. } => {
+                            print!(" !{}", code);
+                            done = true;
+                        }
+                        Reply::TimeExceeded { .. } => {}
+                    }
+                }
+                None => print!("  *"),
+            }
+        }
+        println!();
+        if done {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let result = match Cli::parse().cmd {
+        Cmd::Ping { common, count, interval, ttl, raw } => ping(common, count, interval, ttl, raw),
+        Cmd::Traceroute { common, max_hops, probes } => traceroute(common, max_hops, probes),
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+let dgram = if force_raw { None } else { Socket::new(domain, Type::DGRAM, Some(proto)).ok() };
+```
+
+👉 `SOCK_DGRAM` + `IPPROTO_ICMP` is the **ping socket**: no root needed if your group is in `net.ipv4.ping_group_range` (many distros set `0 2147483647`). The kernel picks the identifier, computes the checksum and only hands us replies for *this* socket.
+
+👉 If that fails we fall back to `SOCK_RAW`, which needs `CAP_NET_RAW` and sees **all** ICMP on the host — hence the `ours(id)` check.
+
+---
+
+```rust
+pkt[0] = if self.v6 { 128 } else { 8 };
+let c = pnet::util::checksum(&pkt, 1);
+```
+
+👉 Echo Request is type **8** in ICMPv4 and **128** in ICMPv6. pnet's `checksum(data, 1)` is the Internet checksum with word 1 (the checksum field itself) skipped. ICMPv6 checksums include the IPv6 pseudo-header, which the kernel fills in for us.
+
+---
+
+```rust
+let hdr = if self.v6 { 40 } else { (inner.first()? & 0x0f) as usize * 4 };
+let (id, seq) = id_seq(inner.get(hdr..hdr + 8)?);
+```
+
+👉 Time Exceeded / Unreachable carry a **copy of our original packet** (IP header + first 8 bytes). That's where our identifier and sequence number are, so we can match a router's answer to the probe that caused it.
+
+---
+
+```rust
+let sock = open(&common, dst, true)?;   // traceroute
+```
+
+👉 On ping sockets, ICMP *errors* don't arrive through `recv` — Linux puts them on the socket error queue (`IP_RECVERR`). A raw socket receives them directly, so traceroute always uses raw.
+
+---
+
+```rust
+let mdev = (rtts.iter().map(|x| x * x).sum::<f64>() / n - avg * avg).max(0.0).sqrt();
+```
+
+👉 **mdev** is the standard deviation of the RTTs — a high value means jitter even when the average looks fine.
+
+---
+
+```rust
+let iface = select::find_interface_by_name(q).map_err(|e| e.to_string())?;
+let src = source(&iface, dst.is_ipv6());
+```
+
+👉 `-I` accepts everything the interface tools accept. We bind to one of its addresses of the right family, and also try `SO_BINDTODEVICE` so the packets really leave through that interface.
+
+👉 With `-6` the first IPv6 address of an interface is usually the link-local `fe80::…`. Binding to it fails with `EINVAL` unless the scope id is set, and it can't reach anything off-link anyway. `source()` therefore prefers a global address and only falls back to link-local, with `scope_id = iface.index`.
+
+---
+
+```rust
+ctrlc::set_handler(move || flag.store(true, Ordering::Relaxed))
+while (count == 0 || sent < count) && !stop.load(Ordering::Relaxed) {
+```
+
+👉 With `-c 0` the only way out is Ctrl+C. The handler just sets a flag: `pause()` wakes within 100 ms (a reply wait in progress runs out its `-W` first), the loop stops, and the statistics are printed as usual. A probe still waiting for its reply when you press Ctrl+C counts as sent but not received, like iputils.
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: reachability and path checks without installing extra tools, with the same interface selection as the rest of `NetworkInterface/`.
+* **What**:
+
+  1. ICMP Echo over unprivileged ping sockets, with a raw-socket fallback.
+  2. IPv4 and IPv6 (global source address preferred), count / interval / TTL / size / wait options; Ctrl+C prints the statistics.
+  3. min/avg/max/mdev statistics and packet loss.
+  4. Traceroute by stepping the TTL and matching Time Exceeded replies to our probes.
+
+---
+
+👉 Example Run (a veth pair in a namespace is enough, no real network needed):
+
+```sh
+sudo ip netns add probe
+sudo ip link add veth0 type veth peer name veth1 netns probe
+sudo ip addr add 10.200.0.1/24 dev veth0 && sudo ip link set veth0 up
+sudo ip netns exec probe ip addr add 10.200.0.2/24 dev veth1
+sudo ip netns exec probe ip link set veth1 up
+
+cargo run -- ping 10.200.0.2 -I veth0 -c 3
+cargo run -- ping -6 ::1 -c 2 -s 1000
+sudo cargo run -- traceroute 1.1.1.1 -I default
+```
+
+Output:
+
+```
+PING 10.200.0.2 (10.200.0.2) 56 data bytes
+64 bytes from 10.200.0.2: icmp_seq=1 time=0.071 ms
+64 bytes from 10.200.0.2: icmp_seq=2 time=0.058 ms
+64 bytes from 10.200.0.2: icmp_seq=3 time=0.062 ms
+
+--- 10.200.0.2 ping statistics ---
+3 packets transmitted, 3 received, 0.0% packet loss
+rtt min/avg/max/mdev = 0.058/0.064/0.071/0.005 ms
+
+traceroute to 1.1.1.1 (1.1.1.1), 30 hops max, 64 byte packets
+ 1  192.168.18.1  0.412 ms  0.388 ms  0.371 ms
+ 2  *  *  *
+ 3  100.64.12.1  6.902 ms  7.114 ms  6.870 ms
+ 4  1.1.1.1  9.231 ms  9.004 ms  9.118 ms
+```
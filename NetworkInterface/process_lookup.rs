@@ -0,0 +1,445 @@
+# 🔹 Big Picture (Why this program exists)
+
+* The sniffer tells us **which addresses** talk. The question we actually get asked is: **"which program is talking to 10.2.3.4?"**
+* Today that means running `ss -tp`, hoping the connection is still open, and guessing.
+* Linux already has the answer, spread over two places in `/proc`:
+
+  1. `/proc/net/tcp`, `tcp6`, `udp`, `udp6` – one line per socket: local address, remote address, **inode**.
+  2. `/proc/<pid>/fd/*` – each open file; sockets show up as links to `socket:[<inode>]`.
+
+* Join them: **5-tuple → inode → PID → command name**. Do it for every new flow the sniffer sees, and print flows with their owning process.
+
+---
+
+# 🔹 Project Layout
+
+```
+flow_procs/
+├── Cargo.toml
+└── src/
+    ├── main.rs    <-- capture loop, flow table, report
+    ├── flow.rs    <-- from the flow export lesson
+    ├── procs.rs   <-- /proc parsing + socket owner lookup
+    └── select.rs  <-- from interface_networks.rs
+```
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+```
+
+👉 Reading other users' `/proc/<pid>/fd` needs root (or `CAP_SYS_PTRACE`) — the sniffer already runs privileged.
+
+---
+
+# 🔹 `src/procs.rs`
+
+```rust
+use pnet::datalink;
+use std::collections::HashMap;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Proto {
+    Tcp,
+    Udp,
+}
+
+/// Owner of a socket.
+#[derive(Clone, Debug)]
+pub struct Owner {
+    pub pid: u32,
+    pub comm: String,
+}
+
+/// One line of /proc/net/{tcp,udp}{,6}.
+struct SocketEntry {
+    proto: Proto,
+    local: SocketAddr,
+    remote: SocketAddr,
+    inode: u64,
+}
+
+/// "0100007F:0050" -> 127.0.0.1:80. The kernel prints each 32-bit word in host (little-endian) order.
+fn parse_addr(s: &str) -> Option<SocketAddr> {
+    let (ip_hex, port_hex) = s.split_once(':')?;
+    let port = u16::from_str_radix(port_hex, 16).ok()?;
+    let ip = match ip_hex.len() {
+        8 => IpAddr::V4(Ipv4Addr::from(u32::from_str_radix(ip_hex, 16).ok()?.swap_bytes())),
+        32 => {
+            let mut bytes = [0u8; 16];
+            for (i, chunk) in bytes.chunks_mut(4).enumerate() {
+                let word = u32::from_str_radix(&ip_hex[i * 8..i * 8 + 8], 16).ok()?;
+                chunk.copy_from_slice(&word.swap_bytes().to_be_bytes());
+            }
+            IpAddr::V6(Ipv6Addr::from(bytes))
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+fn read_proc_net(path: &str, proto: Proto, out: &mut Vec<SocketEntry>) {
+    let text = match fs::read_to_string(path) {
+        Ok(t) => t,
+        Err(_) => return, // e.g. no IPv6 on this host
+    };
+    for line in text.lines().skip(1) {
+        let cols: Vec<&str> = line.split_whitespace().collect();
+        if cols.len() < 10 {
+            continue;
+        }
+        if let (Some(local), Some(remote), Ok(inode)) = (parse_addr(cols[1]), parse_addr(cols[2]), cols[9].parse()) {
+            if inode != 0 {
+                out.push(SocketEntry { proto, local, remote, inode });
+            }
+        }
+    }
+}
+
+/// inode -> owner, by scanning every process's fd directory.
+fn socket_owners() -> HashMap<u64, Owner> {
+    let mut owners = HashMap::new();
+    let procs = match fs::read_dir("/proc") {
+        Ok(p) => p,
+        Err(_) => return owners,
+    };
+    for entry in procs.flatten() {
+        let pid: u32 = match entry.file_name().to_str().and_then(|n| n.parse().ok()) {
+            Some(pid) => pid,
+            None => continue, // not a process directory
+        };
+        let fds = match fs::read_dir(format!("/proc/{}/fd", pid)) {
+            Ok(fds) => fds,
+            Err(_) => continue, // process exited, or no permission
+        };
+        let comm = fs::read_to_string(format!("/proc/{}/comm", pid))
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default();
+        for fd in fds.flatten() {
+            if let Ok(target) = fs::read_link(fd.path()) {
+                let target = target.to_string_lossy();
+                if let Some(inode) = target.strip_prefix("socket:[").and_then(|s| s.strip_suffix(']')) {
+                    if let Ok(inode) = inode.parse() {
+                        owners.entry(inode).or_insert_with(|| Owner { pid, comm: comm.clone() });
+                    }
+                }
+            }
+        }
+    }
+    owners
+}
+
+/// Same address and port. IPv4 sockets opened as IPv6 (dual-stack) show up as ::ffff:a.b.c.d in tcp6.
+fn same(a: SocketAddr, b: SocketAddr) -> bool {
+    a.port() == b.port() && (a.ip() == b.ip() || a.ip().to_canonical() == b.ip().to_canonical())
+}
+
+fn is_unspecified(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_unspecified(),
+        IpAddr::V6(v6) => v6.is_unspecified(),
+    }
+}
+
+/// Snapshot of all sockets and their owners, refreshed on demand.
+pub struct ProcessResolver {
+    sockets: Vec<SocketEntry>,
+    owners: HashMap<u64, Owner>,
+    /// This host's addresses, for the 0.0.0.0 / :: fallback in `bound`.
+    local_ips: Vec<IpAddr>,
+    refreshed: Option<Instant>,
+    min_interval: Duration,
+}
+
+impl ProcessResolver {
+    pub fn new(min_interval: Duration) -> Self {
+        ProcessResolver {
+            sockets: Vec::new(),
+            owners: HashMap::new(),
+            local_ips: Vec::new(),
+            refreshed: None,
+            min_interval,
+        }
+    }
+
+    /// Re-read /proc. Scanning every fd of every process is expensive,
+    /// so this is rate-limited to once per `min_interval`.
+    fn refresh(&mut self) {
+        if self.refreshed.map_or(false, |t| t.elapsed() < self.min_interval) {
+            return;
+        }
+        let mut sockets = Vec::new();
+        read_proc_net("/proc/net/tcp", Proto::Tcp, &mut sockets);
+        read_proc_net("/proc/net/tcp6", Proto::Tcp, &mut sockets);
+        read_proc_net("/proc/net/udp", Proto::Udp, &mut sockets);
+        read_proc_net("/proc/net/udp6", Proto::Udp, &mut sockets);
+        self.sockets = sockets;
+        self.owners = socket_owners();
+        // Addresses come and go (DHCP, VPNs), so they are re-read with the sockets.
+        self.local_ips = datalink::interfaces().iter().flat_map(|i| i.ips.iter().map(|n| n.ip())).collect();
+        self.refreshed = Some(Instant::now());
+    }
+
+    /// Connected socket with exactly this 5-tuple (TCP connection, connected UDP socket).
+    fn exact(&self, proto: Proto, local: SocketAddr, remote: SocketAddr) -> Option<&SocketEntry> {
+        self.sockets.iter().find(|s| s.proto == proto && same(s.local, local) && same(s.remote, remote))
+    }
+
+    /// Is `ip` one of this host's addresses? Anything in 127.0.0.0/8 counts, not just 127.0.0.1.
+    fn is_local(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        ip.is_loopback() || self.local_ips.contains(&ip)
+    }
+
+    /// Unconnected/listening socket bound to the local port (UDP servers, 0.0.0.0 binds).
+    /// A 0.0.0.0 / :: socket only receives packets addressed to this host, so a forwarded
+    /// or bridged flow that merely uses the same port is not its traffic.
+    fn bound(&self, proto: Proto, local: SocketAddr) -> Option<&SocketEntry> {
+        self.sockets.iter().find(|s| {
+            s.proto == proto
+                && s.local.port() == local.port()
+                && ((is_unspecified(s.local.ip()) && self.is_local(local.ip())) || same(s.local, local))
+                && is_unspecified(s.remote.ip())
+        })
+    }
+
+    /// We don't know which end is local, so try both directions. Exact matches win over
+    /// bound ones in *either* direction: a client on port 5353 talking to a local mDNS
+    /// server must not be attributed to the server just because its port matches.
+    fn find(&self, proto: Proto, src: SocketAddr, dst: SocketAddr) -> Option<Owner> {
+        self.exact(proto, src, dst)
+            .or_else(|| self.exact(proto, dst, src))
+            .or_else(|| self.bound(proto, src))
+            .or_else(|| self.bound(proto, dst))
+            .and_then(|s| self.owners.get(&s.inode).cloned())
+    }
+
+    /// Owner of a packet's flow. Refresh once if nothing matches (the socket may be new).
+    pub fn lookup(&mut self, proto: Proto, src: SocketAddr, dst: SocketAddr) -> Option<Owner> {
+        if self.refreshed.is_none() {
+            self.refresh();
+        }
+        if let Some(found) = self.find(proto, src, dst) {
+            return Some(found);
+        }
+        self.refresh();
+        self.find(proto, src, dst)
+    }
+}
+```
+
+---
+
+# 🔹 `src/main.rs`
+
+```rust
+mod flow;
+mod procs;
+mod select;
+
+use clap::Parser;
+use flow::{FlowKey, FlowTable};
+use pnet::datalink::{self, Channel, Config};
+use pnet::packet::ethernet::{EtherTypes, EthernetPacket};
+use pnet::packet::ip::{IpNextHeaderProtocol, IpNextHeaderProtocols};
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::ipv6::Ipv6Packet;
+use pnet::packet::tcp::TcpPacket;
+use pnet::packet::udp::UdpPacket;
+use pnet::packet::Packet;
+use procs::{Owner, ProcessResolver, Proto};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+#[derive(Parser)]
+struct Cli {
+    #[arg(long)]
+    iface: String,
+    /// Only show flows involving this host
+    #[arg(long)]
+    host: Option<IpAddr>,
+    /// Print the flow/process table every N seconds
+    #[arg(long, default_value_t = 5)]
+    every: u64,
+    /// Forget flows (and their owners) idle for this many seconds
+    #[arg(long, default_value_t = 120)]
+    idle: u64,
+}
+
+/// Flow key + TCP flags for TCP/UDP frames.
+fn flow_of(frame: &[u8]) -> Option<(FlowKey, u8)> {
+    let eth = EthernetPacket::new(frame)?;
+    let (src, dst, proto, l4): (IpAddr, IpAddr, IpNextHeaderProtocol, Vec<u8>) = match eth.get_ethertype() {
+        EtherTypes::Ipv4 => {
+            let ip = Ipv4Packet::new(eth.payload())?;
+            (ip.get_source().into(), ip.get_destination().into(), ip.get_next_level_protocol(), ip.payload().to_vec())
+        }
+        EtherTypes::Ipv6 => {
+            let ip = Ipv6Packet::new(eth.payload())?;
+            (ip.get_source().into(), ip.get_destination().into(), ip.get_next_header(), ip.payload().to_vec())
+        }
+        _ => return None,
+    };
+    let (sport, dport, flags) = match proto {
+        IpNextHeaderProtocols::Tcp => TcpPacket::new(&l4).map(|t| (t.get_source(), t.get_destination(), t.get_flags()))?,
+        IpNextHeaderProtocols::Udp => UdpPacket::new(&l4).map(|u| (u.get_source(), u.get_destination(), 0))?,
+        _ => return None,
+    };
+    Some((FlowKey { vlan: None, src, dst, proto: proto.0, sport, dport }, flags))
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let iface = select::interface_or_exit(&cli.iface);
+
+    let mut config = Config::default();
+    config.read_timeout = Some(Duration::from_secs(1));
+
+    let mut resolver = ProcessResolver::new(Duration::from_millis(500));
+    let mut table = FlowTable::default();
+    // Resolved once per flow: the owner doesn't change during a connection.
+    let mut owners: HashMap<FlowKey, Option<Owner>> = HashMap::new();
+    let mut last_print = std::time::Instant::now();
+
+    match datalink::channel(&iface, config) {
+        Ok(Channel::Ethernet(_tx, mut rx)) => {
+            println!("Listening on {}", iface.name);
+            loop {
+                match rx.next() {
+                    Ok(frame) => {
+                        if let Some((key, flags)) = flow_of(frame) {
+                            if cli.host.map_or(true, |h| key.src == h || key.dst == h) {
+                                table.update(key, frame.len(), flags);
+                                owners.entry(key).or_insert_with(|| {
+                                    let proto = if key.proto == 6 { Proto::Tcp } else { Proto::Udp };
+                                    let src = SocketAddr::new(key.src, key.sport);
+                                    let dst = SocketAddr::new(key.dst, key.dport);
+                                    resolver.lookup(proto, src, dst)
+                                });
+                            }
+                        }
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                    Err(e) => eprintln!("Receive error: {:?}", e),
+                }
+
+                if last_print.elapsed() >= Duration::from_secs(cli.every) {
+                    println!("{:<8} {:<16} {:<5} {:<48} {:>8} {:>10}", "PID", "COMMAND", "PROTO", "FLOW", "PKTS", "BYTES");
+                    for (key, stats) in &table.flows {
+                        let (pid, comm) = match owners.get(key).cloned().flatten() {
+                            Some(o) => (o.pid.to_string(), o.comm),
+                            None => ("-".into(), "(not local)".into()),
+                        };
+                        let proto = if key.proto == 6 { "TCP" } else { "UDP" };
+                        let flow = format!("{}:{} -> {}:{}", key.src, key.sport, key.dst, key.dport);
+                        println!("{:<8} {:<16} {:<5} {:<48} {:>8} {:>10}", pid, comm, proto, flow, stats.packets, stats.bytes);
+                    }
+                    println!();
+
+                    // Both tables would otherwise grow for as long as the sniffer runs.
+                    let now = std::time::Instant::now();
+                    for (key, _) in table.expire(now, Duration::MAX, Duration::from_secs(cli.idle)) {
+                        owners.remove(&key);
+                    }
+                    last_print = now;
+                }
+            }
+        }
+        Ok(_) => eprintln!("Unsupported channel type on this platform"),
+        Err(e) => eprintln!("Failed to open datalink channel: {}", e),
+    }
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+8 => IpAddr::V4(Ipv4Addr::from(u32::from_str_radix(ip_hex, 16).ok()?.swap_bytes())),
+```
+
+👉 `/proc/net/tcp` prints addresses as hex of the kernel's in-memory value, which on x86/ARM is **little-endian**: `0100007F` is `127.0.0.1`. IPv6 is four such 32-bit words, each swapped.
+
+---
+
+```rust
+if let Some(inode) = target.strip_prefix("socket:[").and_then(|s| s.strip_suffix(']'))
+```
+
+👉 Every file descriptor in `/proc/<pid>/fd` is a symlink. For sockets the "target" is `socket:[123456]` — the same inode number as in `/proc/net/tcp`. That's the join.
+
+---
+
+```rust
+fn exact(&self, proto, local, remote) -> Option<&SocketEntry>
+fn bound(&self, proto, local) -> Option<&SocketEntry>
+```
+
+👉 Two kinds of matches:
+
+* **exact** – a connected socket (TCP connection, connected UDP socket).
+* **bound** – a UDP server or anything bound to `0.0.0.0:port` with no remote: match on the local port, **if** the packet's address is one of this host's (`is_local`, from `datalink::interfaces()`).
+
+👉 Without that check, `10.2.3.4:22 -> 192.168.1.77:50122` crossing a bridge or router would be pinned on the local sshd, just because sshd listens on `0.0.0.0:22`. A wildcard socket only gets packets addressed to *this* host, so other hosts' flows stay "(not local)".
+
+👉 `to_canonical()` handles dual-stack sockets, where an IPv4 peer appears as `::ffff:10.2.3.4` in `tcp6`.
+
+---
+
+```rust
+self.exact(proto, src, dst)
+    .or_else(|| self.exact(proto, dst, src))
+    .or_else(|| self.bound(proto, src))
+    .or_else(|| self.bound(proto, dst))
+```
+
+👉 A packet may be **outgoing** (local = src) or **incoming** (local = dst), so both are tried — **exact matches first in both directions**, only then the bound fallback. Otherwise a local UDP server on the client's port would "steal" an outgoing flow before the real, connected socket is even tried.
+
+👉 If nothing matches, the socket is probably **brand new** (the packet was the SYN), so we refresh `/proc` once and retry. Refreshes are rate-limited (500 ms) because scanning every fd on a busy host is not free.
+
+---
+
+```rust
+owners.entry(key).or_insert_with(|| resolver.lookup(...));
+```
+
+👉 Each flow is resolved **once**, on its first packet, while the socket still exists. Later, when the program has already closed the connection, we still know who it was.
+
+👉 Flows idle for `--idle` seconds are expired from the flow table **and** from `owners`, so a long-running sniffer doesn't grow without bound.
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: answer "which program is talking to 10.2.3.4?" without `ss` and guesswork.
+* **What**:
+
+  1. Parse `/proc/net/tcp{,6}` and `/proc/net/udp{,6}` → socket inode per 5-tuple.
+  2. Scan `/proc/<pid>/fd` → owning PID per inode, plus `/proc/<pid>/comm` for the name.
+  3. Resolve each new flow once, retrying with a fresh snapshot for new sockets.
+  4. Print the flow table with PID and command.
+
+---
+
+👉 Example Run:
+
+```sh
+sudo cargo run -- --iface eth0 --host 10.2.3.4
+```
+
+Output:
+
+```
+Listening on eth0
+PID      COMMAND          PROTO FLOW                                                 PKTS      BYTES
+48211    curl             TCP   192.168.1.10:51522 -> 10.2.3.4:443                     18      12040
+1022     chronyd          UDP   192.168.1.10:123 -> 10.2.3.4:123                        2        180
+-        (not local)      TCP   10.2.3.4:22 -> 192.168.1.77:50122                      11       1870
+```
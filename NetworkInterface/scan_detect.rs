@@ -0,0 +1,376 @@
+# 🔹 Big Picture (Why this program exists)
+
+* The sniffer already decodes TCP. The **flags** (SYN, ACK, RST, ...) tell us a lot about *behaviour*, not just addresses.
+* Three classic attack patterns are easy to spot with flags + counting:
+
+  * **Port scan** – one source touches **many ports** on a host in a short time.
+  * **Host sweep** – one source touches **many hosts**.
+  * **Half-open (SYN) scan** – SYNs that are never completed with the final ACK.
+  * **SYN flood** – a destination receives a huge number of SYNs per second.
+
+* This program adds a minimal **intrusion-detection signal** to the capture tool: count per source / per destination in a **time window**, compare with **thresholds from a config file**, print a **structured (JSON) alert**.
+
+---
+
+# 🔹 Project Setup
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+serde = { version = "1", features = ["derive"] }
+serde_json = "1"
+toml = "0.8"
+```
+
+Config file `ids.toml` (every value is optional, defaults shown):
+
+```toml
+window_secs = 10          # counting window
+distinct_ports = 50       # ports on one host from one source  -> port_scan
+distinct_hosts = 30       # hosts from one source              -> host_sweep
+half_open = 100           # SYNs never completed from a source -> half_open_scan
+syn_flood_per_sec = 2000  # SYNs per second to one destination -> syn_flood
+cooldown_secs = 60        # don't repeat the same alert for this long
+```
+
+👉 Scans arrive on the internet-facing side, so `--iface default` (resolved by `src/select.rs`, from `interface_networks.rs`) is usually what you want here.
+
+---
+
+# 🔹 Full Code (`src/main.rs`)
+
+```rust
+mod select;
+
+use clap::Parser;
+use pnet::datalink::{self, Channel, Config};
+use pnet::packet::ethernet::{EtherTypes, EthernetPacket};
+use pnet::packet::ip::IpNextHeaderProtocols;
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::ipv6::Ipv6Packet;
+use pnet::packet::tcp::{TcpFlags, TcpPacket};
+use pnet::packet::Packet;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(Parser)]
+struct Cli {
+    #[arg(long)]
+    iface: String,
+    #[arg(long)]
+    promisc: bool,
+    /// Threshold config (TOML); built-in defaults if not given
+    #[arg(long)]
+    config: Option<String>,
+}
+
+/// Thresholds, loaded from the config file.
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+struct Thresholds {
+    window_secs: u64,
+    distinct_ports: usize,
+    distinct_hosts: usize,
+    half_open: usize,
+    syn_flood_per_sec: u64,
+    cooldown_secs: u64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds {
+            window_secs: 10,
+            distinct_ports: 50,
+            distinct_hosts: 30,
+            half_open: 100,
+            syn_flood_per_sec: 2000,
+            cooldown_secs: 60,
+        }
+    }
+}
+
+/// One alert line. Printed as JSON so other tools can consume it.
+#[derive(Serialize)]
+struct Alert {
+    ts: u64,
+    kind: &'static str,
+    src: Option<IpAddr>,
+    dst: Option<IpAddr>,
+    count: u64,
+    threshold: u64,
+    window_secs: u64,
+}
+
+/// What one TCP segment looks like to the detector.
+struct Segment {
+    src: IpAddr,
+    dst: IpAddr,
+    sport: u16,
+    dport: u16,
+    flags: u8,
+}
+
+/// Per-source counters for the current window.
+#[derive(Default)]
+struct SourceState {
+    ports: HashSet<(IpAddr, u16)>,
+    hosts: HashSet<IpAddr>,
+    /// SYNs waiting for the final ACK: (dst, sport, dport)
+    pending: HashSet<(IpAddr, u16, u16)>,
+}
+
+struct Detector {
+    t: Thresholds,
+    window_start: Instant,
+    sources: HashMap<IpAddr, SourceState>,
+    second_start: Instant,
+    syns_to: HashMap<IpAddr, u64>,
+    last_alert: HashMap<(&'static str, Option<IpAddr>, Option<IpAddr>), Instant>,
+}
+
+impl Detector {
+    fn new(t: Thresholds) -> Self {
+        let now = Instant::now();
+        Detector {
+            t,
+            window_start: now,
+            sources: HashMap::new(),
+            second_start: now,
+            syns_to: HashMap::new(),
+            last_alert: HashMap::new(),
+        }
+    }
+
+    fn observe(&mut self, seg: Segment) {
+        self.roll_windows();
+
+        let syn = seg.flags & TcpFlags::SYN != 0;
+        let ack = seg.flags & TcpFlags::ACK != 0;
+        let rst = seg.flags & TcpFlags::RST != 0;
+
+        if syn && !ack {
+            // Connection attempt from seg.src
+            let state = self.sources.entry(seg.src).or_default();
+            state.ports.insert((seg.dst, seg.dport));
+            state.hosts.insert(seg.dst);
+            state.pending.insert((seg.dst, seg.sport, seg.dport));
+            *self.syns_to.entry(seg.dst).or_insert(0) += 1;
+
+            let (ports, hosts, half) = (state.ports.len(), state.hosts.len(), state.pending.len());
+            let window = self.t.window_secs;
+            if ports >= self.t.distinct_ports {
+                self.alert("port_scan", Some(seg.src), None, ports as u64, self.t.distinct_ports as u64, window);
+            }
+            if hosts >= self.t.distinct_hosts {
+                self.alert("host_sweep", Some(seg.src), None, hosts as u64, self.t.distinct_hosts as u64, window);
+            }
+            if half >= self.t.half_open {
+                self.alert("half_open_scan", Some(seg.src), None, half as u64, self.t.half_open as u64, window);
+            }
+            let syns = self.syns_to[&seg.dst];
+            if syns >= self.t.syn_flood_per_sec {
+                self.alert("syn_flood", None, Some(seg.dst), syns, self.t.syn_flood_per_sec, 1);
+            }
+        } else if ack && !syn && !rst {
+            // Final ACK of the handshake (or any later ACK): the connection completed.
+            if let Some(state) = self.sources.get_mut(&seg.src) {
+                state.pending.remove(&(seg.dst, seg.sport, seg.dport));
+            }
+        }
+        // A RST from the scanner after SYN-ACK leaves the entry pending on purpose:
+        // that is exactly what a half-open scan looks like.
+    }
+
+    /// Start a fresh window when the old one is over (simple tumbling windows).
+    fn roll_windows(&mut self) {
+        let now = Instant::now();
+        if now.duration_since(self.window_start) >= Duration::from_secs(self.t.window_secs) {
+            self.sources.clear();
+            self.window_start = now;
+        }
+        if now.duration_since(self.second_start) >= Duration::from_secs(1) {
+            self.syns_to.clear();
+            self.second_start = now;
+        }
+    }
+
+    fn alert(
+        &mut self,
+        kind: &'static str,
+        src: Option<IpAddr>,
+        dst: Option<IpAddr>,
+        count: u64,
+        threshold: u64,
+        window_secs: u64,
+    ) {
+        let now = Instant::now();
+        let cooldown = Duration::from_secs(self.t.cooldown_secs);
+        if let Some(last) = self.last_alert.get(&(kind, src, dst)) {
+            if now.duration_since(*last) < cooldown {
+                return;
+            }
+        }
+        self.last_alert.insert((kind, src, dst), now);
+
+        let ts = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let alert = Alert { ts, kind, src, dst, count, threshold, window_secs };
+        println!("{}", serde_json::to_string(&alert).unwrap());
+    }
+}
+
+/// Pull the TCP fields we need out of an Ethernet frame (IPv4 or IPv6).
+fn tcp_segment(frame: &[u8]) -> Option<Segment> {
+    let eth = EthernetPacket::new(frame)?;
+    let (src, dst, proto, payload): (IpAddr, IpAddr, _, Vec<u8>) = match eth.get_ethertype() {
+        EtherTypes::Ipv4 => {
+            let ip = Ipv4Packet::new(eth.payload())?;
+            (ip.get_source().into(), ip.get_destination().into(), ip.get_next_level_protocol(), ip.payload().to_vec())
+        }
+        EtherTypes::Ipv6 => {
+            let ip = Ipv6Packet::new(eth.payload())?;
+            (ip.get_source().into(), ip.get_destination().into(), ip.get_next_header(), ip.payload().to_vec())
+        }
+        _ => return None,
+    };
+    if proto != IpNextHeaderProtocols::Tcp {
+        return None;
+    }
+    let tcp = TcpPacket::new(&payload)?;
+    Some(Segment { src, dst, sport: tcp.get_source(), dport: tcp.get_destination(), flags: tcp.get_flags() })
+}
+
+fn load_thresholds(path: Option<&str>) -> Thresholds {
+    let path = match path {
+        Some(p) => p,
+        None => return Thresholds::default(),
+    };
+    let text = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("{}: {}", path, e);
+        std::process::exit(1);
+    });
+    toml::from_str(&text).unwrap_or_else(|e| {
+        eprintln!("{}: {}", path, e);
+        std::process::exit(1);
+    })
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let thresholds = load_thresholds(cli.config.as_deref());
+    eprintln!("Thresholds: {:?}", thresholds);
+
+    let iface = select::interface_or_exit(&cli.iface);
+
+    let mut config = Config::default();
+    config.read_timeout = Some(Duration::from_secs(1));
+    config.promiscuous = cli.promisc;
+
+    let mut detector = Detector::new(thresholds);
+
+    match datalink::channel(&iface, config) {
+        Ok(Channel::Ethernet(_tx, mut rx)) => {
+            eprintln!("Watching {} for scans and SYN floods", iface.name);
+            loop {
+                match rx.next() {
+                    Ok(frame) => {
+                        if let Some(seg) = tcp_segment(frame) {
+                            detector.observe(seg);
+                        }
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                    Err(e) => eprintln!("Receive error: {:?}", e),
+                }
+            }
+        }
+        Ok(_) => eprintln!("Unsupported channel type on this platform"),
+        Err(e) => eprintln!("Failed to open datalink channel: {}", e),
+    }
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+let syn = seg.flags & TcpFlags::SYN != 0;
+let ack = seg.flags & TcpFlags::ACK != 0;
+```
+
+👉 The TCP handshake is **SYN → SYN-ACK → ACK**. Looking only at flags we can tell:
+
+* `SYN` alone → someone is **starting** a connection.
+* `ACK` without `SYN` from the same side → the connection was **completed**.
+
+---
+
+```rust
+state.ports.insert((seg.dst, seg.dport));
+state.hosts.insert(seg.dst);
+state.pending.insert((seg.dst, seg.sport, seg.dport));
+```
+
+👉 Per source we keep three sets for the current window:
+
+* `ports` – distinct (host, port) pairs tried → **port scan**.
+* `hosts` – distinct hosts tried → **host sweep**.
+* `pending` – SYNs whose final ACK we haven't seen yet → **half-open scan** (`nmap -sS` sends RST instead of ACK).
+
+👉 Sets (not counters) make repeats harmless: retrying the same port ten times is still one port.
+
+---
+
+```rust
+*self.syns_to.entry(seg.dst).or_insert(0) += 1;
+```
+
+👉 SYN flood is about the **victim**, so this counter is per destination and per second.
+
+---
+
+```rust
+fn roll_windows(&mut self)
+```
+
+👉 Simple **tumbling windows**: when `window_secs` is over, all per-source state is thrown away. Memory stays bounded even when a flood uses random source addresses.
+
+---
+
+```rust
+if now.duration_since(*last) < cooldown { return; }
+```
+
+👉 Without a cooldown, a running scan would print an alert for **every** packet. One alert per (kind, src, dst) per `cooldown_secs` is enough.
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: a minimal IDS signal from the sniffer we already run.
+* **What**:
+
+  1. Decode TCP flags (IPv4 and IPv6).
+  2. Count distinct ports/hosts and uncompleted SYNs per source, SYNs per second per destination.
+  3. Compare with thresholds from a TOML config.
+  4. Print JSON alerts, rate-limited by a cooldown.
+
+---
+
+👉 Example Run:
+
+```sh
+sudo cargo run -- --iface eth0 --config ids.toml
+
+# from another box:
+nmap -sS -p 1-1000 192.168.1.10
+```
+
+Output:
+
+```
+{"ts":1729245567,"kind":"port_scan","src":"192.168.1.50","dst":null,"count":50,"threshold":50,"window_secs":10}
+{"ts":1729245567,"kind":"half_open_scan","src":"192.168.1.50","dst":null,"count":100,"threshold":100,"window_secs":10}
+```
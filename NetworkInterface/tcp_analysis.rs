@@ -0,0 +1,522 @@
+# 🔹 Big Picture (Why this program exists)
+
+* `capture_parse_tcp_ip_ethernet.rs` decodes TCP ports. But a **"the app is slow"** ticket needs more than ports: it needs evidence of *why*.
+* TCP leaves clear traces when things go wrong:
+
+  * **Retransmission** – the same bytes are sent again → something was lost.
+  * **Out-of-order** – a segment arrives with a sequence number *behind* newer data, but too soon to be a retransmission.
+  * **Duplicate ACK** – the receiver repeats the same ACK → it's missing a segment.
+  * **Zero window** – the receiver says "my buffer is full, stop sending" → slow **application**, not network.
+  * **Window full** – the sender has filled the receiver's whole window and must wait.
+  * **RTT** (round-trip time) – how long the network takes to answer.
+
+* This program tracks every TCP connection, counts those events, **estimates RTT** (from SYN/SYN-ACK and from data/ACK pairs), and prints a per-connection **health report**.
+
+---
+
+# 🔹 Project Setup
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+```
+
+👉 "The app is slow" is usually about traffic to the outside: `--iface default` (via `src/select.rs`, from `interface_networks.rs`) watches the interface that carries the default route without looking up its name first.
+
+---
+
+# 🔹 Full Code (`src/main.rs`)
+
+```rust
+mod select;
+
+use clap::Parser;
+use pnet::datalink::{self, Channel, Config};
+use pnet::packet::ethernet::{EtherTypes, EthernetPacket};
+use pnet::packet::ip::IpNextHeaderProtocols;
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::ipv6::Ipv6Packet;
+use pnet::packet::tcp::{TcpFlags, TcpOptionNumbers, TcpPacket};
+use pnet::packet::Packet;
+use std::collections::{HashMap, VecDeque};
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+
+#[derive(Parser)]
+struct Cli {
+    #[arg(long)]
+    iface: String,
+    #[arg(long)]
+    promisc: bool,
+    /// Print a report of all open connections every N seconds (0 = only on close)
+    #[arg(long, default_value_t = 10)]
+    report_every: u64,
+    /// Only report connections that had at least one problem
+    #[arg(long)]
+    problems_only: bool,
+    /// Forget connections with no packets for this many seconds (lost FIN/RST, capture started mid-stream)
+    #[arg(long, default_value_t = 300)]
+    idle_timeout: u64,
+}
+
+/// How long a closed connection is kept (like TIME_WAIT), so the final ACK or a stray
+/// segment after a RST lands on it instead of starting a new, phantom connection.
+const LINGER: Duration = Duration::from_secs(60);
+
+/// Sequence-number comparison with wrap-around (RFC 1982 style).
+fn seq_lt(a: u32, b: u32) -> bool {
+    (a.wrapping_sub(b) as i32) < 0
+}
+fn seq_le(a: u32, b: u32) -> bool {
+    a == b || seq_lt(a, b)
+}
+
+/// State for one direction of a connection (what *this* side has sent).
+#[derive(Default)]
+struct Direction {
+    /// Highest seq + len sent so far
+    max_end: Option<u32>,
+    /// When we last saw new (highest) data
+    max_end_at: Option<Instant>,
+    /// Last ACK number / window this side sent, for dup-ACK detection
+    last_ack: Option<u32>,
+    last_win: u32,
+    dup_acks: u32,
+    /// Window scale from this side's SYN
+    wscale: Option<u8>,
+    /// Segments waiting for an ACK from the other side: (seq end, time sent)
+    in_flight: VecDeque<(u32, Instant)>,
+    packets: u64,
+    bytes: u64,
+    retransmissions: u32,
+    out_of_order: u32,
+    zero_windows: u32,
+    window_full: u32,
+    /// This side has sent a FIN
+    fin: bool,
+}
+
+#[derive(Default)]
+struct Conn {
+    /// Side that sent the first packet we saw (the client if we saw the SYN)
+    a: Option<SocketAddr>,
+    dirs: [Direction; 2],
+    syn_at: Option<Instant>,
+    handshake_rtt: Option<Duration>,
+    rtt_samples: Vec<Duration>,
+    /// Smoothed RTT (RFC 6298), cheap enough to update on every ACK
+    srtt: Option<Duration>,
+    last_seen: Option<Instant>,
+    closed: bool,
+}
+
+impl Conn {
+    fn dir_index(&self, src: SocketAddr) -> usize {
+        if Some(src) == self.a { 0 } else { 1 }
+    }
+
+    fn problems(&self) -> u32 {
+        self.dirs
+            .iter()
+            .map(|d| d.retransmissions + d.out_of_order + d.dup_acks + d.zero_windows + d.window_full)
+            .sum()
+    }
+
+    /// Effective window scale: only used if *both* SYNs carried the option.
+    fn scale(&self, i: usize) -> u8 {
+        match (self.dirs[0].wscale, self.dirs[1].wscale) {
+            (Some(_), Some(_)) => self.dirs[i].wscale.unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    /// Median of the recent samples, for the report only: sorting on every segment is too slow.
+    fn median_rtt(&self) -> Option<Duration> {
+        if self.rtt_samples.is_empty() {
+            return self.handshake_rtt;
+        }
+        let mut s = self.rtt_samples.clone();
+        s.sort();
+        Some(s[s.len() / 2]) // median is robust against delayed-ACK outliers
+    }
+
+    fn on_segment(&mut self, src: SocketAddr, tcp: &TcpPacket, payload_len: u32, now: Instant) {
+        if self.a.is_none() {
+            self.a = Some(src);
+        }
+        self.last_seen = Some(now);
+        let i = self.dir_index(src);
+        let o = 1 - i;
+        let flags = tcp.get_flags();
+        let syn = flags & TcpFlags::SYN != 0;
+        let fin = flags & TcpFlags::FIN != 0;
+        let rst = flags & TcpFlags::RST != 0;
+        let ack = flags & TcpFlags::ACK != 0;
+        let seq = tcp.get_sequence();
+        // SYN and FIN each use one sequence number
+        let seg_len = payload_len + syn as u32 + fin as u32;
+        let end = seq.wrapping_add(seg_len);
+
+        // ---- handshake: window scale + RTT from SYN / SYN-ACK ----
+        if syn {
+            self.dirs[i].wscale = tcp
+                .get_options_iter()
+                .find(|o| o.get_number() == TcpOptionNumbers::WSCALE)
+                .and_then(|o| o.payload().first().copied());
+            if !ack {
+                self.syn_at = Some(now);
+            } else if let Some(t) = self.syn_at.take() {
+                self.handshake_rtt = Some(now.duration_since(t));
+            }
+        }
+
+        // ---- retransmission / out-of-order ----
+        let rtt = self.srtt.or(self.handshake_rtt).unwrap_or(Duration::from_millis(3));
+        let d = &mut self.dirs[i];
+        d.packets += 1;
+        d.bytes += payload_len as u64;
+        let mut retransmitted = false;
+        if seg_len > 0 {
+            match d.max_end {
+                Some(max_end) if seq_lt(seq, max_end) => {
+                    // Data "from the past". If it shows up shortly after newer data,
+                    // the network reordered it; otherwise the sender sent it again.
+                    let since_newest = d.max_end_at.map(|t| now.duration_since(t)).unwrap_or_default();
+                    if since_newest < rtt {
+                        d.out_of_order += 1;
+                    } else {
+                        d.retransmissions += 1;
+                        retransmitted = true;
+                    }
+                }
+                _ => {}
+            }
+            if d.max_end.map_or(true, |m| seq_lt(m, end)) {
+                d.max_end = Some(end);
+                d.max_end_at = Some(now);
+            }
+            // Karn's rule: never take an RTT sample from a retransmitted segment.
+            if retransmitted {
+                d.in_flight.retain(|(e, _)| !seq_le(*e, end));
+            } else {
+                d.in_flight.push_back((end, now));
+                if d.in_flight.len() > 1024 {
+                    d.in_flight.pop_front();
+                }
+            }
+        }
+
+        // ---- window: zero window, window full ----
+        let win = (tcp.get_window() as u32) << self.scale(i);
+        let d = &mut self.dirs[i];
+        if win == 0 && !rst && !syn {
+            d.zero_windows += 1;
+        }
+
+        // ---- ACK side: duplicate ACKs + RTT samples for the other direction ----
+        if ack && !syn {
+            let ackno = tcp.get_acknowledgement();
+            let pure_ack = payload_len == 0 && !fin && !rst;
+            if pure_ack && d.last_ack == Some(ackno) && d.last_win == win {
+                d.dup_acks += 1;
+            }
+            d.last_ack = Some(ackno);
+            d.last_win = win;
+
+            // Everything in the other side's flight list up to `ackno` is now acknowledged.
+            let other = &mut self.dirs[o];
+            while let Some(&(e, sent)) = other.in_flight.front() {
+                if !seq_le(e, ackno) {
+                    break;
+                }
+                other.in_flight.pop_front();
+                // One sample per ACK is enough (the newest segment it covers).
+                if other.in_flight.front().map_or(true, |&(e2, _)| !seq_le(e2, ackno)) {
+                    let sample = now.duration_since(sent);
+                    self.rtt_samples.push(sample);
+                    self.srtt = Some(self.srtt.map_or(sample, |s| (s * 7 + sample) / 8));
+                }
+            }
+            if self.rtt_samples.len() > 256 {
+                self.rtt_samples.drain(..128);
+            }
+        }
+
+        // ---- window full: this side filled the receiver's advertised window ----
+        if payload_len > 0 {
+            let (peer_ack, peer_win) = (self.dirs[o].last_ack, self.dirs[o].last_win);
+            if let Some(peer_ack) = peer_ack {
+                if peer_win > 0 && end == peer_ack.wrapping_add(peer_win) {
+                    self.dirs[i].window_full += 1;
+                }
+            }
+        }
+
+        // Half-close is normal (one side is done sending, the other isn't):
+        // the connection is over only after a RST or a FIN from both sides.
+        self.dirs[i].fin |= fin;
+        if rst || (self.dirs[0].fin && self.dirs[1].fin) {
+            self.closed = true;
+        }
+    }
+}
+
+/// Connections are stored under a direction-independent key.
+fn conn_key(a: SocketAddr, b: SocketAddr) -> (SocketAddr, SocketAddr) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+fn print_report(key: &(SocketAddr, SocketAddr), c: &Conn) {
+    let a = c.a.unwrap_or(key.0);
+    let b = if a == key.0 { key.1 } else { key.0 };
+    let ms = |d: Option<Duration>| d.map(|d| format!("{:.2}ms", d.as_secs_f64() * 1000.0)).unwrap_or_else(|| "-".into());
+    let verdict = if c.problems() == 0 {
+        "OK"
+    } else if c.dirs.iter().any(|d| d.zero_windows > 0) {
+        "RECEIVER SLOW (zero window)"
+    } else if c.dirs.iter().any(|d| d.retransmissions > 0) {
+        "PACKET LOSS (retransmissions)"
+    } else {
+        "DEGRADED"
+    };
+
+    println!("{} <-> {}  [{}]", a, b, verdict);
+    println!("  RTT: handshake {}  data {} ({} samples)", ms(c.handshake_rtt), ms(c.median_rtt()), c.rtt_samples.len());
+    for (name, d) in [(format!("{} ->", a), &c.dirs[0]), (format!("{} ->", b), &c.dirs[1])] {
+        println!(
+            "  {:<24} pkts={} bytes={} retrans={} ooo={} dup_ack={} zero_win={} win_full={}",
+            name, d.packets, d.bytes, d.retransmissions, d.out_of_order, d.dup_acks, d.zero_windows, d.window_full
+        );
+    }
+}
+
+/// Source/destination sockets + TCP segment bytes, for IPv4 or IPv6.
+fn tcp_of(frame: &[u8]) -> Option<(SocketAddr, SocketAddr, Vec<u8>)> {
+    let eth = EthernetPacket::new(frame)?;
+    let (src, dst, segment): (IpAddr, IpAddr, Vec<u8>) = match eth.get_ethertype() {
+        EtherTypes::Ipv4 => {
+            let ip = Ipv4Packet::new(eth.payload())?;
+            if ip.get_next_level_protocol() != IpNextHeaderProtocols::Tcp {
+                return None;
+            }
+            (ip.get_source().into(), ip.get_destination().into(), ip.payload().to_vec())
+        }
+        EtherTypes::Ipv6 => {
+            let ip = Ipv6Packet::new(eth.payload())?;
+            if ip.get_next_header() != IpNextHeaderProtocols::Tcp {
+                return None;
+            }
+            (ip.get_source().into(), ip.get_destination().into(), ip.payload().to_vec())
+        }
+        _ => return None,
+    };
+    let tcp = TcpPacket::new(&segment)?;
+    let (sport, dport) = (tcp.get_source(), tcp.get_destination());
+    Some((SocketAddr::new(src, sport), SocketAddr::new(dst, dport), segment))
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let iface = select::interface_or_exit(&cli.iface);
+
+    let mut config = Config::default();
+    config.read_timeout = Some(Duration::from_secs(1));
+    config.promiscuous = cli.promisc;
+
+    let mut conns: HashMap<(SocketAddr, SocketAddr), Conn> = HashMap::new();
+    let mut last_report = Instant::now();
+    let mut last_sweep = Instant::now();
+    let idle_timeout = Duration::from_secs(cli.idle_timeout);
+
+    match datalink::channel(&iface, config) {
+        Ok(Channel::Ethernet(_tx, mut rx)) => {
+            println!("Analysing TCP on {}", iface.name);
+            loop {
+                match rx.next() {
+                    Ok(frame) => {
+                        if let Some((src, dst, segment)) = tcp_of(frame) {
+                            if let Some(tcp) = TcpPacket::new(&segment) {
+                                let hdr = tcp.get_data_offset() as usize * 4;
+                                let payload_len = segment.len().saturating_sub(hdr) as u32;
+                                let key = conn_key(src, dst);
+                                let syn = tcp.get_flags() & TcpFlags::SYN != 0;
+                                // A SYN on a lingering 4-tuple is a new connection reusing the ports.
+                                if syn && conns.get(&key).map_or(false, |c| c.closed) {
+                                    conns.remove(&key);
+                                }
+                                // Only a SYN or data starts tracking: a lone ACK/FIN/RST for a
+                                // connection we never saw (or already forgot) tells us nothing.
+                                if conns.contains_key(&key) || syn || payload_len > 0 {
+                                    let conn = conns.entry(key).or_default();
+                                    if !conn.closed {
+                                        conn.on_segment(src, &tcp, payload_len, Instant::now());
+                                        if conn.closed && (!cli.problems_only || conn.problems() > 0) {
+                                            print_report(&key, conn);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                    Err(e) => eprintln!("Receive error: {:?}", e),
+                }
+
+                // Drop closed connections after LINGER (already reported), and evict
+                // connections that went quiet without a clean close.
+                if last_sweep.elapsed() >= Duration::from_secs(1) {
+                    let now = Instant::now();
+                    conns.retain(|key, conn| {
+                        let quiet = conn.last_seen.map_or(Duration::MAX, |t| now.duration_since(t));
+                        if conn.closed {
+                            return quiet < LINGER;
+                        }
+                        let idle = quiet >= idle_timeout;
+                        if idle && (!cli.problems_only || conn.problems() > 0) {
+                            print_report(key, conn);
+                        }
+                        !idle
+                    });
+                    last_sweep = now;
+                }
+
+                if cli.report_every > 0 && last_report.elapsed() >= Duration::from_secs(cli.report_every) {
+                    let open = conns.values().filter(|c| !c.closed).count();
+                    println!("---- {} open connections ----", open);
+                    for (key, conn) in conns.iter().filter(|(_, c)| !c.closed) {
+                        if !cli.problems_only || conn.problems() > 0 {
+                            print_report(key, conn);
+                        }
+                    }
+                    last_report = Instant::now();
+                }
+            }
+        }
+        Ok(_) => eprintln!("Unsupported channel type on this platform"),
+        Err(e) => eprintln!("Failed to open datalink channel: {}", e),
+    }
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+fn seq_lt(a: u32, b: u32) -> bool { (a.wrapping_sub(b) as i32) < 0 }
+```
+
+👉 TCP sequence numbers are 32 bits and **wrap around**. `a < b` would break after 4 GB; "is the difference negative?" works across the wrap.
+
+---
+
+```rust
+Some(max_end) if seq_lt(seq, max_end) => {
+    if since_newest < rtt { d.out_of_order += 1; } else { d.retransmissions += 1; }
+```
+
+👉 Data *behind* the highest byte already sent is either:
+
+* **out-of-order** – it arrives very soon (less than one RTT) after newer data: the network swapped two packets.
+* **retransmission** – it arrives later: the sender timed out or got dup ACKs and sent it **again**.
+
+👉 This is the same heuristic Wireshark uses (3 ms if no RTT is known yet).
+
+---
+
+```rust
+if pure_ack && d.last_ack == Some(ackno) && d.last_win == win { d.dup_acks += 1; }
+```
+
+👉 A **duplicate ACK** = no data, same ACK number, same window. Three of them trigger **fast retransmit** in the sender.
+
+---
+
+```rust
+let win = (tcp.get_window() as u32) << self.scale(i);
+```
+
+👉 The 16-bit window field is **scaled** by the shift from the SYN's window-scale option (only if both sides sent it). Without this, "window full" would be wrong on any modern connection.
+
+---
+
+```rust
+while let Some(&(e, sent)) = other.in_flight.front() {
+    if !seq_le(e, ackno) { break; }
+    ...
+    self.rtt_samples.push(now.duration_since(sent));
+```
+
+👉 **Data/ACK RTT**: remember when each segment was sent; when an ACK covers it, `now - sent` is one RTT sample.
+
+👉 **Karn's rule**: retransmitted segments are removed from the list — we can't know which copy the ACK answers.
+
+👉 The report shows the **median**, because delayed ACKs produce occasional 40 ms outliers. Sorting the samples is only done for the report; the per-segment retransmission heuristic uses a **smoothed RTT** (`srtt = 7/8·srtt + 1/8·sample`, as in RFC 6298), which costs nothing to update.
+
+---
+
+```rust
+self.dirs[i].fin |= fin;
+if rst || (self.dirs[0].fin && self.dirs[1].fin) { self.closed = true; }
+```
+
+👉 A FIN only closes **one direction**; the other side may keep sending (HTTP/1.0 requests, `shutdown(SHUT_WR)`). The connection is finished after a **RST** or once **both** sides sent FIN.
+
+👉 A closed connection is reported once, then **lingers** for `LINGER` (60 s, like TIME_WAIT) before it is dropped. The last ACK after FIN/FIN, or retransmissions racing a RST, land on it and are ignored. Removing it right away would let `or_default()` create a phantom connection from that ACK, which then shows up in the periodic report and gets a second report when it goes idle.
+
+👉 For the same reason, only a **SYN or data** starts tracking a new 4-tuple; a stray ACK, FIN or RST does not. A SYN on a lingering 4-tuple starts a fresh connection (port reuse).
+
+👉 Connections that never close cleanly (lost FIN, capture started mid-stream, peer vanished) are evicted after `--idle-timeout` seconds without packets, with a final report.
+
+---
+
+```rust
+if peer_win > 0 && end == peer_ack.wrapping_add(peer_win) { self.dirs[i].window_full += 1; }
+```
+
+👉 **Window full**: the sender's data reaches exactly the right edge of what the receiver allowed (`ack + window`). It now has to wait — a sign the window (or buffer size) is the bottleneck.
+
+---
+
+# 🔹 Reading the Report
+
+* **Zero window** on the *server* side → the **server application** isn't reading fast enough. Not a network problem.
+* **Retransmissions + dup ACKs** → **packet loss** on the path.
+* **Window full** with low RTT → receive buffer too small for the bandwidth.
+* High **handshake RTT** but low **data RTT** → slow accept / SYN queue on the server.
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: "the app is slow" tickets need evidence, not guesses.
+* **What**:
+
+  1. Track both directions of every TCP connection (IPv4 and IPv6).
+  2. Detect retransmissions, out-of-order segments, duplicate ACKs, zero-window and window-full.
+  3. Estimate RTT from SYN/SYN-ACK and from data/ACK pairs (Karn's rule).
+  4. Print a health report when a connection closes (RST or FIN both ways) or goes idle, and periodically for open ones.
+
+---
+
+👉 Example Run:
+
+```sh
+sudo cargo run -- --iface eth0 --problems-only --report-every 30
+```
+
+Output:
+
+```
+Analysing TCP on eth0
+192.168.1.10:51522 <-> 10.0.4.20:443  [PACKET LOSS (retransmissions)]
+  RTT: handshake 21.40ms  data 22.85ms (311 samples)
+  192.168.1.10:51522 ->    pkts=402 bytes=18211 retrans=0 ooo=0 dup_ack=14 zero_win=0 win_full=0
+  10.0.4.20:443 ->         pkts=611 bytes=8830120 retrans=9 ooo=1 dup_ack=0 zero_win=0 win_full=3
+192.168.1.10:40110 <-> 10.0.4.31:5432  [RECEIVER SLOW (zero window)]
+  RTT: handshake 0.41ms  data 0.52ms (80 samples)
+  192.168.1.10:40110 ->    pkts=95 bytes=7104 retrans=0 ooo=0 dup_ack=0 zero_win=6 win_full=0
+  10.0.4.31:5432 ->        pkts=140 bytes=1988820 retrans=0 ooo=0 dup_ack=0 zero_win=0 win_full=5
+```
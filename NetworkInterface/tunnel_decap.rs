@@ -0,0 +1,350 @@
+# 🔹 Big Picture (Why this program exists)
+
+* Containers and overlay networks (Docker, Kubernetes, OpenStack) **wrap** packets inside other packets:
+
+  * **VXLAN** – Ethernet frame inside UDP port **4789**.
+  * **Geneve** – like VXLAN, UDP port **6081**, with optional TLV options.
+  * **GRE** – IP protocol **47**, can carry IPv4, IPv6 or a whole Ethernet frame.
+  * **IP-in-IP / 6in4** – IP protocol **4** (IPv4 inside) or **41** (IPv6 inside).
+
+* The basic sniffer stops at the **outer** UDP/IP header, so all you see is "node A talks to node B on 4789" — useless for debugging pod traffic.
+* This program recognises those encapsulations and **recursively decodes** the inner Ethernet/IP packet, printing **both** the outer and inner addresses.
+
+---
+
+# 🔹 Project Setup
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+```
+
+👉 Tunnel endpoints are known by their address more often than by interface name: with `src/select.rs` (from `interface_networks.rs`), `--iface 192.0.2.10` captures on whichever interface owns the VTEP address.
+
+---
+
+# 🔹 Full Code (`src/main.rs`)
+
+```rust
+mod select;
+
+use clap::Parser;
+use pnet::datalink::{self, Channel, Config};
+use pnet::packet::ethernet::{EtherType, EtherTypes, EthernetPacket};
+use pnet::packet::ip::{IpNextHeaderProtocol, IpNextHeaderProtocols};
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::ipv6::Ipv6Packet;
+use pnet::packet::tcp::TcpPacket;
+use pnet::packet::udp::UdpPacket;
+use pnet::packet::Packet;
+use std::time::Duration;
+
+const VXLAN_PORT: u16 = 4789;
+const GENEVE_PORT: u16 = 6081;
+/// "Transparent Ethernet Bridging": the GRE/Geneve payload is a full Ethernet frame.
+const ETHERTYPE_TEB: EtherType = EtherType(0x6558);
+/// Stop after this many nested layers (protects against crafted packets).
+const MAX_DEPTH: usize = 8;
+
+#[derive(Parser)]
+struct Cli {
+    #[arg(long)]
+    iface: String,
+    #[arg(long)]
+    promisc: bool,
+    /// Only print packets that contain at least one tunnel
+    #[arg(long)]
+    tunnels_only: bool,
+}
+
+/// Collected description of one packet, outermost layer first.
+#[derive(Default)]
+struct Layers {
+    parts: Vec<String>,
+    tunnels: usize,
+}
+
+impl Layers {
+    fn push(&mut self, s: String) {
+        self.parts.push(s);
+    }
+}
+
+/// Checked at the top of every decoder: IP-in-IP chains never reach decode_ethernet.
+fn too_deep(depth: usize, out: &mut Layers) -> bool {
+    if depth > MAX_DEPTH {
+        out.push("(too deep)".into());
+    }
+    depth > MAX_DEPTH
+}
+
+fn decode_ethernet(data: &[u8], depth: usize, out: &mut Layers) {
+    if too_deep(depth, out) {
+        return;
+    }
+    let eth = match EthernetPacket::new(data) {
+        Some(eth) => eth,
+        None => {
+            out.push("Malformed ethernet packet".into());
+            return;
+        }
+    };
+    // Only show MACs for inner frames; the outer ones are always our own NIC/gateway.
+    if depth > 0 {
+        out.push(format!("ETH {} -> {}", eth.get_source(), eth.get_destination()));
+    }
+    decode_ethertype(eth.get_ethertype(), eth.payload(), depth, out);
+}
+
+fn decode_ethertype(ethertype: EtherType, data: &[u8], depth: usize, out: &mut Layers) {
+    match ethertype {
+        EtherTypes::Ipv4 => decode_ipv4(data, depth, out),
+        EtherTypes::Ipv6 => decode_ipv6(data, depth, out),
+        ETHERTYPE_TEB => decode_ethernet(data, depth + 1, out),
+        other => out.push(format!("ethertype {}", other)),
+    }
+}
+
+fn decode_ipv4(data: &[u8], depth: usize, out: &mut Layers) {
+    if too_deep(depth, out) {
+        return;
+    }
+    let ip = match Ipv4Packet::new(data) {
+        Some(ip) => ip,
+        None => return out.push("Malformed IPv4 packet".into()),
+    };
+    out.push(format!("IPv4 {} -> {}", ip.get_source(), ip.get_destination()));
+    if ip.get_fragment_offset() != 0 {
+        return out.push("(fragment)".into());
+    }
+    decode_ip_payload(ip.get_next_level_protocol(), ip.payload(), depth, out);
+}
+
+fn decode_ipv6(data: &[u8], depth: usize, out: &mut Layers) {
+    if too_deep(depth, out) {
+        return;
+    }
+    let ip = match Ipv6Packet::new(data) {
+        Some(ip) => ip,
+        None => return out.push("Malformed IPv6 packet".into()),
+    };
+    out.push(format!("IPv6 {} -> {}", ip.get_source(), ip.get_destination()));
+    decode_ip_payload(ip.get_next_header(), ip.payload(), depth, out);
+}
+
+fn decode_ip_payload(proto: IpNextHeaderProtocol, data: &[u8], depth: usize, out: &mut Layers) {
+    if too_deep(depth, out) {
+        return;
+    }
+    match proto {
+        IpNextHeaderProtocols::Tcp => {
+            if let Some(tcp) = TcpPacket::new(data) {
+                out.push(format!("TCP {} -> {}", tcp.get_source(), tcp.get_destination()));
+            }
+        }
+        IpNextHeaderProtocols::Udp => decode_udp(data, depth, out),
+        IpNextHeaderProtocols::Gre => decode_gre(data, depth, out),
+        // IP-in-IP (4) and 6in4 / IPv6-in-anything (41)
+        IpNextHeaderProtocols::Ipv4 => {
+            out.tunnels += 1;
+            out.push("IPIP".into());
+            decode_ipv4(data, depth + 1, out);
+        }
+        IpNextHeaderProtocols::Ipv6 => {
+            out.tunnels += 1;
+            out.push("6in4".into());
+            decode_ipv6(data, depth + 1, out);
+        }
+        other => out.push(format!("proto {}", other)),
+    }
+}
+
+fn decode_udp(data: &[u8], depth: usize, out: &mut Layers) {
+    let udp = match UdpPacket::new(data) {
+        Some(udp) => udp,
+        None => return out.push("Malformed UDP packet".into()),
+    };
+    out.push(format!("UDP {} -> {}", udp.get_source(), udp.get_destination()));
+    match udp.get_destination() {
+        VXLAN_PORT => decode_vxlan(udp.payload(), depth, out),
+        GENEVE_PORT => decode_geneve(udp.payload(), depth, out),
+        _ => {}
+    }
+}
+
+/// VXLAN header (8 bytes): flags(1) reserved(3) VNI(3) reserved(1), then an Ethernet frame.
+fn decode_vxlan(data: &[u8], depth: usize, out: &mut Layers) {
+    if data.len() < 8 || data[0] & 0x08 == 0 {
+        return out.push("VXLAN (bad header)".into());
+    }
+    let vni = u32::from_be_bytes([0, data[4], data[5], data[6]]);
+    out.tunnels += 1;
+    out.push(format!("VXLAN vni={}", vni));
+    decode_ethernet(&data[8..], depth + 1, out);
+}
+
+/// Geneve header: ver+optlen(1) flags(1) protocol(2) VNI(3) reserved(1), then optlen*4 bytes of options.
+fn decode_geneve(data: &[u8], depth: usize, out: &mut Layers) {
+    if data.len() < 8 {
+        return out.push("Geneve (bad header)".into());
+    }
+    let opt_len = (data[0] & 0x3f) as usize * 4;
+    let protocol = EtherType(u16::from_be_bytes([data[2], data[3]]));
+    let vni = u32::from_be_bytes([0, data[4], data[5], data[6]]);
+    if data.len() < 8 + opt_len {
+        return out.push(format!("Geneve vni={} (truncated options)", vni));
+    }
+    out.tunnels += 1;
+    out.push(format!("Geneve vni={} opts={}B", vni, opt_len));
+    decode_ethertype(protocol, &data[8 + opt_len..], depth + 1, out);
+}
+
+/// GRE header: flags+version(2) protocol(2), then optional checksum, key and sequence (4 bytes each).
+fn decode_gre(data: &[u8], depth: usize, out: &mut Layers) {
+    if data.len() < 4 {
+        return out.push("GRE (bad header)".into());
+    }
+    let flags = data[0];
+    let version = data[1] & 0x07;
+    let protocol = EtherType(u16::from_be_bytes([data[2], data[3]]));
+    if version != 0 {
+        // Version 1 is PPTP's enhanced GRE, which carries PPP: not decoded here.
+        return out.push(format!("GRE v{} proto {}", version, protocol));
+    }
+
+    let mut offset = 4;
+    if flags & 0x80 != 0 {
+        offset += 4; // checksum + reserved
+    }
+    let mut key = None;
+    if flags & 0x20 != 0 {
+        if data.len() < offset + 4 {
+            return out.push("GRE (truncated)".into());
+        }
+        key = Some(u32::from_be_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]]));
+        offset += 4;
+    }
+    if flags & 0x10 != 0 {
+        offset += 4; // sequence number
+    }
+    if data.len() < offset {
+        return out.push("GRE (truncated)".into());
+    }
+
+    out.tunnels += 1;
+    match key {
+        Some(k) => out.push(format!("GRE key={}", k)),
+        None => out.push("GRE".into()),
+    }
+    decode_ethertype(protocol, &data[offset..], depth + 1, out);
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let iface = select::interface_or_exit(&cli.iface);
+
+    let mut config = Config::default();
+    config.read_timeout = Some(Duration::from_secs(1));
+    config.promiscuous = cli.promisc;
+
+    match datalink::channel(&iface, config) {
+        Ok(Channel::Ethernet(_tx, mut rx)) => {
+            println!("Listening on {}", iface.name);
+            loop {
+                match rx.next() {
+                    Ok(frame) => {
+                        let mut layers = Layers::default();
+                        decode_ethernet(frame, 0, &mut layers);
+                        if cli.tunnels_only && layers.tunnels == 0 {
+                            continue;
+                        }
+                        println!("{}", layers.parts.join(" | "));
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                    Err(e) => eprintln!("Receive error: {:?}", e),
+                }
+            }
+        }
+        Ok(_) => eprintln!("Unsupported channel type on this platform"),
+        Err(e) => eprintln!("Failed to open datalink channel: {}", e),
+    }
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+fn decode_ethernet(data: &[u8], depth: usize, out: &mut Layers)
+fn decode_ipv4(...) / decode_ipv6(...) / decode_udp(...) / decode_gre(...)
+```
+
+👉 Each layer has its own small function. A tunnel decoder simply **calls the decoder for what's inside** (`decode_ethernet` or `decode_ipv4`) — that's the recursion. A VXLAN packet inside GRE inside IPIP just works.
+
+👉 `depth` grows with every tunnel and stops at `MAX_DEPTH`, so a crafted packet can't make us recurse forever. The check (`too_deep`) sits in **every** decoder: a chain of IPIP headers never passes through `decode_ethernet`.
+
+---
+
+```rust
+let vni = u32::from_be_bytes([0, data[4], data[5], data[6]]);
+```
+
+👉 The **VNI** (VXLAN/Geneve network identifier) is 24 bits. It tells you *which* overlay network the inner frame belongs to — the overlay version of a VLAN ID.
+
+---
+
+```rust
+let opt_len = (data[0] & 0x3f) as usize * 4;
+```
+
+👉 Geneve can carry **options** after its 8-byte header. Their length is stored in 4-byte units; we skip them to reach the inner frame. Geneve also has a `protocol` field, so it can carry Ethernet (`0x6558`) **or** IP directly.
+
+---
+
+```rust
+if flags & 0x80 != 0 { offset += 4; }  // checksum
+if flags & 0x20 != 0 { key = ...; }    // key
+if flags & 0x10 != 0 { offset += 4; }  // sequence
+```
+
+👉 GRE's header size depends on three flag bits. The **key** is often used as a tunnel/tenant ID, so we print it.
+
+---
+
+```rust
+IpNextHeaderProtocols::Ipv4 => { out.push("IPIP".into()); decode_ipv4(data, depth + 1, out); }
+IpNextHeaderProtocols::Ipv6 => { out.push("6in4".into()); decode_ipv6(data, depth + 1, out); }
+```
+
+👉 IP-in-IP has **no tunnel header at all**: the IP protocol number alone (4 or 41) says "another IP packet follows".
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: overlay networks hide the real traffic inside UDP/GRE/IP, and that's where the bugs are.
+* **What**:
+
+  1. Recognise VXLAN (UDP 4789), Geneve (UDP 6081), GRE (proto 47), IPIP (4) and 6in4 (41).
+  2. Skip their headers and **recursively** decode the inner Ethernet or IP packet.
+  3. Print every layer, outer first, so both outer and inner addresses are visible.
+
+---
+
+👉 Example Run:
+
+```sh
+sudo cargo run -- --iface eth0 --tunnels-only
+```
+
+Output:
+
+```
+Listening on eth0
+IPv4 192.168.10.11 -> 192.168.10.12 | UDP 48211 -> 4789 | VXLAN vni=1 | ETH 7a:3f:11:02:aa:10 -> 5e:91:0c:7d:21:07 | IPv4 10.244.1.5 -> 10.244.2.9 | TCP 43120 -> 8080
+IPv4 192.168.10.11 -> 192.168.10.20 | GRE key=100 | IPv4 172.16.0.2 -> 172.16.5.1 | UDP 53321 -> 53
+IPv4 203.0.113.1 -> 198.51.100.7 | 6in4 | IPv6 2001:db8::1 -> 2001:db8:5::10 | TCP 51000 -> 443
+```
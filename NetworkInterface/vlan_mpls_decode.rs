@@ -0,0 +1,414 @@
+# 🔹 Big Picture (Why this program exists)
+
+* On a **trunk port** most frames carry extra headers between Ethernet and IP:
+
+  * **802.1Q VLAN tag** (ethertype `0x8100`) – VLAN ID, priority (PCP), drop-eligible bit (DEI).
+  * **802.1ad / QinQ** (`0x88a8`, old `0x9100`) – a provider tag *outside* the customer tag.
+  * **MPLS** (`0x8847` / `0x8848`) – a stack of 4-byte labels.
+
+* The original sniffer does `match eth.get_ethertype()` and only knows `Ipv4` / `Ipv6`, so tagged frames fall into `_ => { /* other ethertypes */ }` and are **skipped**.
+* This program **peels off** tags and labels, then continues into the inner IPv4/IPv6 packet as usual.
+* Most NICs **strip the 802.1Q tag in hardware** (`rx-vlan-offload`) and hand it to the kernel as metadata, so an `AF_PACKET` socket sees the frame *untagged*. Turn that off on the capture interface first: `sudo ethtool -K eth1 rxvlan off`.
+* It also introduces a small **flow table** whose key includes the **VLAN ID**, and a `--vlan` filter. The flow code lives in its own file (`flow.rs`) so later lessons can reuse it.
+
+---
+
+# 🔹 Project Layout
+
+```
+sniffer_vlan/
+├── Cargo.toml
+└── src/
+    ├── main.rs   <-- capture + decode
+    ├── flow.rs   <-- FlowKey + FlowTable (reused later)
+    └── select.rs <-- from interface_networks.rs
+```
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+```
+
+---
+
+# 🔹 `src/flow.rs`
+
+```rust
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::Instant;
+
+/// What identifies one conversation. VLAN is part of the key:
+/// the same 5-tuple on VLAN 10 and VLAN 20 are two different flows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FlowKey {
+    pub vlan: Option<u16>,
+    pub src: IpAddr,
+    pub dst: IpAddr,
+    pub proto: u8,
+    pub sport: u16,
+    pub dport: u16,
+}
+
+/// Counters for one flow.
+#[derive(Clone, Debug)]
+pub struct FlowStats {
+    pub packets: u64,
+    pub bytes: u64,
+    pub first_seen: Instant,
+    pub last_seen: Instant,
+}
+
+#[derive(Default)]
+pub struct FlowTable {
+    pub flows: HashMap<FlowKey, FlowStats>,
+}
+
+impl FlowTable {
+    /// Count one packet of `bytes` length for `key`.
+    pub fn update(&mut self, key: FlowKey, bytes: usize) {
+        let now = Instant::now();
+        let stats = self.flows.entry(key).or_insert(FlowStats {
+            packets: 0,
+            bytes: 0,
+            first_seen: now,
+            last_seen: now,
+        });
+        stats.packets += 1;
+        stats.bytes += bytes as u64;
+        stats.last_seen = now;
+    }
+
+    /// Print the flows, biggest first.
+    pub fn print_top(&self, n: usize) {
+        let mut flows: Vec<_> = self.flows.iter().collect();
+        flows.sort_by(|a, b| b.1.bytes.cmp(&a.1.bytes));
+        println!("{:<6} {:<5} {:<40} {:>8} {:>10}", "VLAN", "PROTO", "FLOW", "PKTS", "BYTES");
+        for (key, stats) in flows.into_iter().take(n) {
+            let vlan = key.vlan.map(|v| v.to_string()).unwrap_or_else(|| "-".into());
+            let flow = format!("{}:{} -> {}:{}", key.src, key.sport, key.dst, key.dport);
+            println!("{:<6} {:<5} {:<40} {:>8} {:>10}", vlan, key.proto, flow, stats.packets, stats.bytes);
+        }
+    }
+}
+```
+
+---
+
+# 🔹 `src/main.rs`
+
+```rust
+mod flow;
+mod select;
+
+use clap::Parser;
+use flow::{FlowKey, FlowTable};
+use pnet::datalink::{self, Channel, Config};
+use pnet::packet::ethernet::{EtherType, EtherTypes, EthernetPacket};
+use pnet::packet::ip::{IpNextHeaderProtocol, IpNextHeaderProtocols};
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::ipv6::Ipv6Packet;
+use pnet::packet::tcp::TcpPacket;
+use pnet::packet::udp::UdpPacket;
+use pnet::packet::vlan::VlanPacket;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+const ETHERTYPE_MPLS: EtherType = EtherType(0x8847);
+const ETHERTYPE_MPLS_MCAST: EtherType = EtherType(0x8848);
+
+#[derive(Parser)]
+struct Cli {
+    #[arg(long)]
+    iface: String,
+    #[arg(long)]
+    promisc: bool,
+    /// Only show frames carrying this VLAN ID (in any tag of the stack)
+    #[arg(long)]
+    vlan: Option<u16>,
+    /// Print the flow table every N seconds (0 = never)
+    #[arg(long, default_value_t = 10)]
+    flows_every: u64,
+}
+
+/// One 802.1Q / 802.1ad tag.
+struct VlanTag {
+    tpid: EtherType,
+    id: u16,
+    pcp: u8,
+    dei: bool,
+}
+
+/// One MPLS label stack entry.
+struct MplsLabel {
+    label: u32,
+    tc: u8,
+    bottom: bool,
+    ttl: u8,
+}
+
+/// Everything found between the Ethernet header and the inner packet.
+struct Decoded<'a> {
+    tags: Vec<VlanTag>,
+    labels: Vec<MplsLabel>,
+    /// Inner ethertype (IPv4 / IPv6 / ...), after all tags
+    ethertype: EtherType,
+    payload: &'a [u8],
+}
+
+impl Decoded<'_> {
+    /// The VLAN used for filters and flow keys: the innermost (customer) tag.
+    fn vlan(&self) -> Option<u16> {
+        self.tags.last().map(|t| t.id)
+    }
+}
+
+/// Peel VLAN tags and MPLS labels off until we reach something that isn't a tag.
+/// Works on the raw frame, so the inner payload borrows from `frame` directly.
+fn decode_l2(frame: &[u8]) -> Option<Decoded<'_>> {
+    let eth = EthernetPacket::new(frame)?;
+    let mut tags = Vec::new();
+    let mut labels = Vec::new();
+    let mut ethertype = eth.get_ethertype();
+    // Ethernet header is always 14 bytes; tags are part of the "payload".
+    let mut payload = &frame[14..];
+
+    loop {
+        match ethertype {
+            EtherTypes::Vlan | EtherTypes::PBridge | EtherTypes::QinQ => {
+                let vlan = match VlanPacket::new(payload) {
+                    Some(v) => v,
+                    None => break, // truncated tag: stop here, caller sees the tag ethertype
+                };
+                tags.push(VlanTag {
+                    tpid: ethertype,
+                    id: vlan.get_vlan_identifier(),
+                    pcp: vlan.get_priority_code_point().0,
+                    dei: vlan.get_drop_eligible_indicator() == 1,
+                });
+                ethertype = vlan.get_ethertype();
+                payload = &payload[4..];
+            }
+            ETHERTYPE_MPLS | ETHERTYPE_MPLS_MCAST => {
+                // Labels are 4 bytes each; the S bit marks the last one.
+                while payload.len() >= 4 {
+                    let word = u32::from_be_bytes([payload[0], payload[1], payload[2], payload[3]]);
+                    let label = MplsLabel {
+                        label: word >> 12,
+                        tc: ((word >> 9) & 0x7) as u8,
+                        bottom: (word >> 8) & 0x1 == 1,
+                        ttl: (word & 0xff) as u8,
+                    };
+                    payload = &payload[4..];
+                    let bottom = label.bottom;
+                    labels.push(label);
+                    if bottom {
+                        break;
+                    }
+                }
+                // MPLS has no "next protocol" field: guess from the IP version nibble.
+                ethertype = match payload.first().map(|b| b >> 4) {
+                    Some(4) => EtherTypes::Ipv4,
+                    Some(6) => EtherTypes::Ipv6,
+                    _ => EtherType(0), // pseudowire / unknown
+                };
+                break;
+            }
+            _ => break,
+        }
+    }
+
+    Some(Decoded { tags, labels, ethertype, payload })
+}
+
+fn describe_l2(d: &Decoded) -> String {
+    let mut parts = Vec::new();
+    for t in &d.tags {
+        let kind = if t.tpid == EtherTypes::Vlan { "802.1Q" } else { "802.1ad" };
+        parts.push(format!("{} vlan={} pcp={} dei={}", kind, t.id, t.pcp, t.dei as u8));
+    }
+    for l in &d.labels {
+        parts.push(format!("MPLS label={} tc={} s={} ttl={}", l.label, l.tc, l.bottom as u8, l.ttl));
+    }
+    parts.join(" | ")
+}
+
+/// Decode the inner IP packet; returns the flow key (without VLAN) and a printable line.
+fn decode_l3(ethertype: EtherType, payload: &[u8]) -> Option<(FlowKey, String)> {
+    let (src, dst, proto, l4): (IpAddr, IpAddr, IpNextHeaderProtocol, &[u8]) = match ethertype {
+        EtherTypes::Ipv4 => {
+            let ip = Ipv4Packet::new(payload)?;
+            let hdr = ip.get_header_length() as usize * 4;
+            let end = (ip.get_total_length() as usize).min(payload.len());
+            if hdr < 20 || hdr > end {
+                return None;
+            }
+            (ip.get_source().into(), ip.get_destination().into(), ip.get_next_level_protocol(), &payload[hdr..end])
+        }
+        EtherTypes::Ipv6 => {
+            let ip = Ipv6Packet::new(payload)?;
+            let end = (40 + ip.get_payload_length() as usize).min(payload.len());
+            (ip.get_source().into(), ip.get_destination().into(), ip.get_next_header(), &payload[40..end])
+        }
+        _ => return None,
+    };
+
+    let (sport, dport, name) = match proto {
+        IpNextHeaderProtocols::Tcp => TcpPacket::new(l4).map(|t| (t.get_source(), t.get_destination(), "TCP"))?,
+        IpNextHeaderProtocols::Udp => UdpPacket::new(l4).map(|u| (u.get_source(), u.get_destination(), "UDP"))?,
+        _ => (0, 0, "IP"),
+    };
+    let key = FlowKey { vlan: None, src, dst, proto: proto.0, sport, dport };
+    let line = format!("{} {}:{} -> {}:{}", name, src, sport, dst, dport);
+    Some((key, line))
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let iface = select::interface_or_exit(&cli.iface);
+
+    let mut config = Config::default();
+    config.read_timeout = Some(Duration::from_secs(1));
+    config.promiscuous = cli.promisc;
+
+    let mut flows = FlowTable::default();
+    let mut last_report = Instant::now();
+
+    match datalink::channel(&iface, config) {
+        Ok(Channel::Ethernet(_tx, mut rx)) => {
+            println!("Listening on {}", iface.name);
+            // With rx-vlan-offload on, the NIC removes the outer 802.1Q tag before we see the frame.
+            eprintln!("note: no VLAN tags showing up? run `sudo ethtool -K {} rxvlan off`", iface.name);
+            loop {
+                match rx.next() {
+                    Ok(frame) => {
+                        let l2 = match decode_l2(frame) {
+                            Some(l2) => l2,
+                            None => {
+                                eprintln!("Malformed ethernet packet");
+                                continue;
+                            }
+                        };
+
+                        if cli.vlan.is_some() && !l2.tags.iter().any(|t| Some(t.id) == cli.vlan) {
+                            continue;
+                        }
+
+                        let tags = describe_l2(&l2);
+                        match decode_l3(l2.ethertype, l2.payload) {
+                            Some((mut key, line)) => {
+                                key.vlan = l2.vlan();
+                                flows.update(key, frame.len());
+                                if tags.is_empty() {
+                                    println!("{}", line);
+                                } else {
+                                    println!("[{}] {}", tags, line);
+                                }
+                            }
+                            None if !tags.is_empty() => println!("[{}] ethertype {}", tags, l2.ethertype),
+                            None => {}
+                        }
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                    Err(e) => eprintln!("Receive error: {:?}", e),
+                }
+
+                if cli.flows_every > 0 && last_report.elapsed() >= Duration::from_secs(cli.flows_every) {
+                    flows.print_top(20);
+                    last_report = Instant::now();
+                }
+            }
+        }
+        Ok(_) => eprintln!("Unsupported channel type on this platform"),
+        Err(e) => eprintln!("Failed to open datalink channel: {}", e),
+    }
+}
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+EtherTypes::Vlan | EtherTypes::PBridge | EtherTypes::QinQ => {
+    let vlan = VlanPacket::new(payload)...;
+    ethertype = vlan.get_ethertype();
+    payload = &payload[4..];
+}
+```
+
+👉 A VLAN tag is only **4 bytes**: 3 bits PCP, 1 bit DEI, 12 bits VLAN ID, then the *real* ethertype. pnet's `VlanPacket` reads them for us.
+
+👉 It's a **loop**, because QinQ means a tag inside a tag (`0x88a8` outer, `0x8100` inner).
+
+---
+
+```sh
+ethtool -k eth1 | grep rx-vlan-offload   # "on" = tags are stripped before capture
+sudo ethtool -K eth1 rxvlan off
+```
+
+👉 With **rx-vlan-offload** the NIC (or the kernel, for veth and bridges) pops the 802.1Q tag and stores the VLAN ID in the packet's metadata. `tcpdump` gets it back from `PACKET_AUXDATA` and re-inserts it, but pnet's `datalink` channel only returns the bytes — so the tag is simply gone and the frame looks like plain IPv4 on no VLAN.
+
+👉 Switching the offload off makes the tag stay in the frame. Only the outermost tag is offloaded, so with QinQ the inner tag is visible either way. Turn it back on (`rxvlan on`) when you're done; it saves CPU on a busy trunk.
+
+---
+
+```rust
+label: word >> 12, tc: ((word >> 9) & 0x7) as u8, bottom: (word >> 8) & 0x1 == 1, ttl: (word & 0xff) as u8,
+```
+
+👉 One MPLS entry = 20-bit label, 3-bit traffic class, 1-bit **bottom of stack**, 8-bit TTL. We read entries until the bottom bit is set.
+
+👉 MPLS doesn't say what's inside. The first nibble of an IP header is its version, so `4` → IPv4 and `6` → IPv6.
+
+---
+
+```rust
+fn vlan(&self) -> Option<u16> { self.tags.last().map(|t| t.id) }
+```
+
+👉 The **innermost** tag is the customer VLAN — the one people mean when they say "VLAN 100". `--vlan` matches **any** tag in the stack, so you can filter on the provider tag too.
+
+---
+
+```rust
+pub struct FlowKey { pub vlan: Option<u16>, pub src, pub dst, pub proto, pub sport, pub dport }
+```
+
+👉 A flow table counts packets/bytes per conversation. Putting `vlan` in the key keeps overlapping address spaces on different VLANs apart.
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: trunk ports carry mostly tagged traffic, and the old sniffer skipped it.
+* **What**:
+
+  1. Decode 802.1Q / 802.1ad tags (VLAN ID, PCP, DEI) and MPLS label stacks.
+  2. Continue into the inner IPv4/IPv6 + TCP/UDP.
+  3. Filter with `--vlan <id>`.
+  4. Count flows in a `FlowTable` keyed on VLAN + 5-tuple.
+
+---
+
+👉 Example Run:
+
+```sh
+sudo ethtool -K eth1 rxvlan off
+sudo cargo run -- --iface eth1 --promisc --vlan 100
+```
+
+Output:
+
+```
+Listening on eth1
+note: no VLAN tags showing up? run `sudo ethtool -K eth1 rxvlan off`
+[802.1ad vlan=2000 pcp=0 dei=0 | 802.1Q vlan=100 pcp=5 dei=0] UDP 10.1.0.5:5060 -> 10.1.0.9:5060
+[802.1Q vlan=100 pcp=0 dei=0] TCP 10.1.0.7:51234 -> 10.1.0.1:443
+VLAN   PROTO FLOW                                         PKTS      BYTES
+100    6     10.1.0.7:51234 -> 10.1.0.1:443                 812    1024331
+100    17    10.1.0.5:5060 -> 10.1.0.9:5060                  44      23190
+```
@@ -0,0 +1,447 @@
+# 🔹 Big Picture (Why this program exists)
+
+* The sniffer runs on **headless boxes**. Colleagues want to watch a capture from their **laptop browser** without SSH + tmux.
+* Idea: add an optional **embedded web server** to the sniffer:
+
+  * `GET /` → a small bundled HTML page.
+  * `GET /ws?host=..&port=..&proto=..` → a **WebSocket** that streams decoded packets and live statistics as **JSON**.
+  * Filters run on the **server**, so a browser watching one host doesn't receive the whole firehose.
+
+* It reuses the same decode path as the sniffer (Ethernet → IPv4/IPv6 → TCP/UDP), just returning a struct instead of printing.
+* Runs on **Tokio** (see `Tokio/tokio.rs`): the capture loop stays a normal blocking thread, the web side is async.
+
+---
+
+# 🔹 Project Layout
+
+```
+sniffer_web/
+├── Cargo.toml
+├── static/
+│   └── index.html   <-- bundled into the binary with include_str!
+└── src/
+    ├── main.rs
+    └── select.rs   <-- from interface_networks.rs
+```
+
+```toml
+[dependencies]
+pnet = "0.34.0"
+clap = { version = "4", features = ["derive"] }
+tokio = { version = "1", features = ["full"] }
+axum = { version = "0.7", features = ["ws"] }
+serde = { version = "1", features = ["derive"] }
+serde_json = "1"
+```
+
+---
+
+# 🔹 `src/main.rs`
+
+```rust
+mod select;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::response::{Html, IntoResponse};
+use axum::routing::get;
+use axum::Router;
+use clap::Parser;
+use pnet::datalink::{self, Channel, Config};
+use pnet::packet::ethernet::{EtherTypes, EthernetPacket};
+use pnet::packet::ip::{IpNextHeaderProtocol, IpNextHeaderProtocols};
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::ipv6::Ipv6Packet;
+use pnet::packet::tcp::TcpPacket;
+use pnet::packet::udp::UdpPacket;
+use pnet::packet::Packet;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+
+const INDEX_HTML: &str = include_str!("../static/index.html");
+
+#[derive(Parser)]
+struct Cli {
+    #[arg(long)]
+    iface: String,
+    #[arg(long)]
+    promisc: bool,
+    /// Where the web UI listens. Default is localhost only; use 0.0.0.0 to share it.
+    #[arg(long, default_value = "127.0.0.1:8088")]
+    web: String,
+}
+
+/// One decoded packet, as sent to the browser.
+#[derive(Clone, Serialize)]
+struct DecodedPacket {
+    ts: f64,
+    len: usize,
+    proto: String,
+    src: Option<IpAddr>,
+    dst: Option<IpAddr>,
+    sport: Option<u16>,
+    dport: Option<u16>,
+    summary: String,
+}
+
+/// Live counters, sent once per second.
+#[derive(Clone, Serialize)]
+struct StatsSnapshot {
+    packets: u64,
+    bytes: u64,
+    malformed: u64,
+    lagged: u64,
+    pps: u64,
+}
+
+/// Everything that goes over the WebSocket, tagged with "type".
+#[derive(Clone, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Event {
+    Packet(DecodedPacket),
+    Stats(StatsSnapshot),
+}
+
+#[derive(Default)]
+struct Counters {
+    packets: AtomicU64,
+    bytes: AtomicU64,
+    malformed: AtomicU64,
+    /// Packets a slow browser missed because it couldn't keep up
+    lagged: AtomicU64,
+}
+
+#[derive(Clone)]
+struct AppState {
+    events: broadcast::Sender<Event>,
+    counters: Arc<Counters>,
+}
+
+/// Server-side filter, from the WebSocket URL's query string.
+#[derive(Deserialize, Default)]
+struct Filter {
+    host: Option<IpAddr>,
+    port: Option<u16>,
+    proto: Option<String>,
+}
+
+impl Filter {
+    fn matches(&self, p: &DecodedPacket) -> bool {
+        self.host.map_or(true, |h| p.src == Some(h) || p.dst == Some(h))
+            && self.port.map_or(true, |port| p.sport == Some(port) || p.dport == Some(port))
+            && self.proto.as_ref().map_or(true, |proto| p.proto.eq_ignore_ascii_case(proto))
+    }
+}
+
+/// Same layers as the sniffer's decode, but returns data instead of printing.
+fn decode(frame: &[u8]) -> Option<DecodedPacket> {
+    let ts = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs_f64();
+    let eth = EthernetPacket::new(frame)?;
+    let mut p = DecodedPacket {
+        ts,
+        len: frame.len(),
+        proto: format!("{}", eth.get_ethertype()),
+        src: None,
+        dst: None,
+        sport: None,
+        dport: None,
+        summary: String::new(),
+    };
+    let (proto, l4): (IpNextHeaderProtocol, Vec<u8>) = match eth.get_ethertype() {
+        EtherTypes::Ipv4 => {
+            let ip = Ipv4Packet::new(eth.payload())?;
+            p.src = Some(ip.get_source().into());
+            p.dst = Some(ip.get_destination().into());
+            (ip.get_next_level_protocol(), ip.payload().to_vec())
+        }
+        EtherTypes::Ipv6 => {
+            let ip = Ipv6Packet::new(eth.payload())?;
+            p.src = Some(ip.get_source().into());
+            p.dst = Some(ip.get_destination().into());
+            (ip.get_next_header(), ip.payload().to_vec())
+        }
+        _ => {
+            p.summary = format!("{} -> {}", eth.get_source(), eth.get_destination());
+            return Some(p);
+        }
+    };
+    p.proto = format!("{}", proto);
+    match proto {
+        IpNextHeaderProtocols::Tcp => {
+            let tcp = TcpPacket::new(&l4)?;
+            p.proto = "TCP".into();
+            p.sport = Some(tcp.get_source());
+            p.dport = Some(tcp.get_destination());
+            p.summary = format!("flags=0x{:02x} win={}", tcp.get_flags(), tcp.get_window());
+        }
+        IpNextHeaderProtocols::Udp => {
+            let udp = UdpPacket::new(&l4)?;
+            p.proto = "UDP".into();
+            p.sport = Some(udp.get_source());
+            p.dport = Some(udp.get_destination());
+            p.summary = format!("len={}", udp.get_length());
+        }
+        _ => {}
+    }
+    Some(p)
+}
+
+/// Blocking capture loop on its own OS thread; publishes into the broadcast channel.
+fn capture(iface_name: String, promisc: bool, state: AppState) {
+    let iface = select::interface_or_exit(&iface_name);
+
+    let mut config = Config::default();
+    config.read_timeout = Some(Duration::from_secs(1));
+    config.promiscuous = promisc;
+
+    match datalink::channel(&iface, config) {
+        Ok(Channel::Ethernet(_tx, mut rx)) => {
+            println!("Listening on {}", iface.name);
+            loop {
+                match rx.next() {
+                    Ok(frame) => {
+                        state.counters.packets.fetch_add(1, Ordering::Relaxed);
+                        state.counters.bytes.fetch_add(frame.len() as u64, Ordering::Relaxed);
+                        match decode(frame) {
+                            // `send` only fails when nobody is connected: that's fine.
+                            Some(p) => {
+                                let _ = state.events.send(Event::Packet(p));
+                            }
+                            None => {
+                                state.counters.malformed.fetch_add(1, Ordering::Relaxed);
+                            }
+                        }
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                    Err(e) => eprintln!("Receive error: {:?}", e),
+                }
+            }
+        }
+        Ok(_) => eprintln!("Unsupported channel type on this platform"),
+        Err(e) => eprintln!("Failed to open datalink channel: {}", e),
+    }
+}
+
+/// Once per second: publish a statistics event.
+async fn stats_task(state: AppState) {
+    let mut ticker = tokio::time::interval(Duration::from_secs(1));
+    let mut last_packets = 0;
+    loop {
+        ticker.tick().await;
+        let c = &state.counters;
+        let packets = c.packets.load(Ordering::Relaxed);
+        let snapshot = StatsSnapshot {
+            packets,
+            bytes: c.bytes.load(Ordering::Relaxed),
+            malformed: c.malformed.load(Ordering::Relaxed),
+            lagged: c.lagged.load(Ordering::Relaxed),
+            pps: packets - last_packets,
+        };
+        last_packets = packets;
+        let _ = state.events.send(Event::Stats(snapshot));
+    }
+}
+
+async fn index() -> Html<&'static str> {
+    Html(INDEX_HTML)
+}
+
+async fn ws_handler(ws: WebSocketUpgrade, Query(filter): Query<Filter>, State(state): State<AppState>) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| client(socket, filter, state))
+}
+
+/// One browser connection: forward matching events until it disconnects.
+async fn client(mut socket: WebSocket, filter: Filter, state: AppState) {
+    let mut events = state.events.subscribe();
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(n)) => {
+                // This browser is too slow: skip ahead instead of slowing everyone down.
+                state.counters.lagged.fetch_add(n, Ordering::Relaxed);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        if let Event::Packet(p) = &event {
+            if !filter.matches(p) {
+                continue;
+            }
+        }
+        let json = serde_json::to_string(&event).unwrap();
+        if socket.send(Message::Text(json)).await.is_err() {
+            break; // browser closed the tab
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let cli = Cli::parse();
+
+    let (events, _) = broadcast::channel(4096);
+    let state = AppState { events, counters: Arc::new(Counters::default()) };
+
+    // pnet's rx.next() blocks, so capture gets a real thread, not a Tokio task.
+    {
+        let (iface, promisc, state) = (cli.iface.clone(), cli.promisc, state.clone());
+        std::thread::spawn(move || capture(iface, promisc, state));
+    }
+    tokio::spawn(stats_task(state.clone()));
+
+    let app = Router::new()
+        .route("/", get(index))
+        .route("/ws", get(ws_handler))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(&cli.web).await?;
+    println!("Web UI on http://{}", cli.web);
+    axum::serve(listener, app).await
+}
+```
+
+---
+
+# 🔹 `static/index.html`
+
+```html
+<!doctype html>
+<html>
+<head>
+  <meta charset="utf-8">
+  <title>sniffer</title>
+  <style>
+    body { font-family: monospace; margin: 1em; }
+    #stats { margin-bottom: .5em; color: #555; }
+    table { border-collapse: collapse; width: 100%; }
+    td, th { padding: 2px 8px; text-align: left; border-bottom: 1px solid #eee; }
+  </style>
+</head>
+<body>
+  <form id="f">
+    host <input name="host" size="20"> port <input name="port" size="6">
+    proto <select name="proto"><option></option><option>TCP</option><option>UDP</option></select>
+    <button>apply</button>
+  </form>
+  <div id="stats">connecting...</div>
+  <table><thead><tr><th>time</th><th>proto</th><th>source</th><th>destination</th><th>len</th><th>info</th></tr></thead>
+  <tbody id="rows"></tbody></table>
+<script>
+  const MAX_ROWS = 500;
+  let ws;
+  function connect() {
+    const params = new URLSearchParams(new FormData(document.getElementById('f')));
+    for (const [k, v] of [...params]) if (!v) params.delete(k);
+    if (ws) ws.close();
+    ws = new WebSocket(`ws://${location.host}/ws?${params}`);
+    ws.onmessage = (msg) => {
+      const ev = JSON.parse(msg.data);
+      if (ev.type === 'stats') {
+        document.getElementById('stats').textContent =
+          `${ev.pps} pps | ${ev.packets} packets | ${ev.bytes} bytes | malformed ${ev.malformed} | dropped for slow clients ${ev.lagged}`;
+        return;
+      }
+      const ep = (ip, port) => ip ? (port != null ? `${ip}:${port}` : ip) : '';
+      const tr = document.createElement('tr');
+      for (const v of [new Date(ev.ts * 1000).toISOString().slice(11, 23), ev.proto,
+                       ep(ev.src, ev.sport), ep(ev.dst, ev.dport), ev.len, ev.summary]) {
+        const td = document.createElement('td'); td.textContent = v; tr.appendChild(td);
+      }
+      const rows = document.getElementById('rows');
+      rows.prepend(tr);
+      while (rows.children.length > MAX_ROWS) rows.lastChild.remove();
+    };
+    ws.onclose = () => document.getElementById('stats').textContent = 'disconnected';
+  }
+  document.getElementById('f').onsubmit = (e) => { e.preventDefault(); connect(); };
+  connect();
+</script>
+</body>
+</html>
+```
+
+---
+
+# 🔹 Code Walkthrough
+
+```rust
+std::thread::spawn(move || capture(iface, promisc, state));
+```
+
+👉 `rx.next()` is a **blocking** call. Running it inside a Tokio task would block a runtime worker thread (see "Common pitfalls" in `Tokio/tokio.rs`). So capture gets its own OS thread, and only talks to the async side through a channel.
+
+---
+
+```rust
+let (events, _) = broadcast::channel(4096);
+```
+
+👉 `tokio::sync::broadcast` = one sender, many receivers. Every browser calls `subscribe()` and gets **its own copy** of each event.
+
+👉 The buffer is bounded. A slow browser gets `RecvError::Lagged(n)`: it skips `n` events and carries on — the capture thread is **never** slowed down by a client. We count those skips and show them in the stats.
+
+---
+
+```rust
+async fn ws_handler(ws: WebSocketUpgrade, Query(filter): Query<Filter>, ...)
+```
+
+👉 Filters come from the URL (`/ws?host=10.0.0.5&proto=TCP`) and are applied **per client on the server**, before JSON encoding. Each browser only pays for what it asked for.
+
+---
+
+```rust
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Event { Packet(DecodedPacket), Stats(StatsSnapshot) }
+```
+
+👉 Both kinds of messages share one socket; the `"type"` field tells the page which is which: `{"type":"packet",...}` or `{"type":"stats",...}`.
+
+---
+
+```rust
+const INDEX_HTML: &str = include_str!("../static/index.html");
+```
+
+👉 The page is **compiled into the binary**: copy one file to the headless box, nothing else to deploy.
+
+---
+
+# 🔹 Summary in Simple Words
+
+* **Why**: watch a capture from a laptop while the tool runs on a headless box.
+* **What**:
+
+  1. Blocking capture thread → decode → `tokio::sync::broadcast`.
+  2. axum serves a bundled page on `/` and a WebSocket on `/ws`.
+  3. Each client has server-side filters (host, port, protocol).
+  4. Live stats (pps, bytes, malformed, lagged) every second.
+
+---
+
+👉 Example Run:
+
+```sh
+sudo cargo run -- --iface eth0 --web 0.0.0.0:8088
+```
+
+Output:
+
+```
+Web UI on http://0.0.0.0:8088
+Listening on eth0
+```
+
+Then open `http://<box>:8088/` in a browser, or test from the shell:
+
+```sh
+websocat "ws://<box>:8088/ws?proto=TCP&port=443"
+{"type":"packet","ts":1729251201.5531,"len":66,"proto":"TCP","src":"192.168.1.10","dst":"140.82.112.4","sport":51522,"dport":443,"summary":"flags=0x10 win=501"}
+{"type":"stats","packets":1822,"bytes":1290110,"malformed":0,"lagged":0,"pps":311}
+```
+
+👉 Security note: the default bind is `127.0.0.1`. Packet contents can be sensitive — use an SSH tunnel (`ssh -L 8088:localhost:8088 box`) rather than `0.0.0.0` on untrusted networks.
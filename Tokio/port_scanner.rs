@@ -0,0 +1,380 @@
+
+* A **port scanner** asks, for every `host:port`: *is anything listening here, and does the firewall let me reach it?*
+* Doing that one port at a time is slow (a filtered port can take a full timeout to answer). Tokio lets us run **hundreds of probes at once** on a few OS threads.
+* This scanner does:
+
+  * **TCP connect scans** — `open` (handshake completed), `closed` (RST → connection refused), `filtered` (no answer before the timeout).
+  * **UDP probes** — `open` (got a reply), `closed` (ICMP port unreachable), `open|filtered` (silence; UDP can't tell them apart).
+  * **Concurrency limit**, **per-probe timeout** and **retries** for probes that got no answer.
+  * Optional **banner grabbing** on open TCP ports.
+
+* Use it only against hosts you own or are allowed to test — it's meant to check your **own** firewall rules without installing nmap.
+
+Good mental model: a `Semaphore` is a bowl of N tickets; each probe takes one before it starts and gives it back when it's done, so at most N probes are in flight.
+
+---
+
+## Complete port scanner
+
+`Cargo.toml`:
+
+```toml
+[package]
+name = "tokio_scan"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+tokio = { version = "1", features = ["full"] }
+clap = { version = "4", features = ["derive"] }
+ipnetwork = "0.20"
+```
+
+`src/main.rs`:
+
+```rust
+use clap::Parser;
+use ipnetwork::IpNetwork;
+use std::io::ErrorKind;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{lookup_host, TcpStream, UdpSocket};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time::timeout;
+
+#[derive(Parser)]
+struct Cli {
+    /// Hosts: addresses, CIDR ranges (10.0.0.0/28) or names; comma separated
+    #[arg(value_delimiter = ',', required = true)]
+    targets: Vec<String>,
+
+    /// Ports: "22,80,443" and/or ranges "8000-8100"
+    #[arg(short, long, default_value = "1-1024")]
+    ports: String,
+
+    /// Probe UDP instead of TCP
+    #[arg(long)]
+    udp: bool,
+
+    /// Maximum probes in flight
+    #[arg(short, long, default_value_t = 256)]
+    concurrency: usize,
+
+    /// Per-probe timeout in milliseconds
+    #[arg(short, long, default_value_t = 1000)]
+    timeout_ms: u64,
+
+    /// Extra attempts for probes that got no answer
+    #[arg(short, long, default_value_t = 1)]
+    retries: u32,
+
+    /// Read a banner from open TCP ports
+    #[arg(short, long)]
+    banner: bool,
+
+    /// Also print closed and filtered ports
+    #[arg(short, long)]
+    all: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum State {
+    Open,
+    Closed,
+    Filtered,
+    /// UDP only: no reply and no ICMP error. Either open and silent, or dropped by a firewall.
+    OpenFiltered,
+}
+
+impl State {
+    fn as_str(&self) -> &'static str {
+        match self {
+            State::Open => "open",
+            State::Closed => "closed",
+            State::Filtered => "filtered",
+            State::OpenFiltered => "open|filtered",
+        }
+    }
+}
+
+struct Outcome {
+    addr: SocketAddr,
+    state: State,
+    banner: Option<String>,
+}
+
+/// "22,80,8000-8010" -> [22, 80, 8000, ..., 8010]
+fn parse_ports(spec: &str) -> Result<Vec<u16>, String> {
+    let mut ports = Vec::new();
+    for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((a, b)) => {
+                let a: u16 = a.parse().map_err(|_| format!("bad port '{}'", a))?;
+                let b: u16 = b.parse().map_err(|_| format!("bad port '{}'", b))?;
+                if a > b {
+                    return Err(format!("bad range '{}'", part));
+                }
+                ports.extend(a..=b);
+            }
+            None => ports.push(part.parse().map_err(|_| format!("bad port '{}'", part))?),
+        }
+    }
+    ports.sort_unstable();
+    ports.dedup();
+    Ok(ports)
+}
+
+/// Largest number of hosts we agree to scan (a /16). An IPv6 /64 would never finish.
+const MAX_HOSTS: u128 = 1 << 16;
+
+/// Hosts in a range, without the network/broadcast addresses of IPv4 ranges.
+fn host_count(net: &IpNetwork) -> u128 {
+    match net {
+        IpNetwork::V4(n) if n.prefix() < 31 => n.size() as u128 - 2,
+        IpNetwork::V4(n) => n.size() as u128,
+        IpNetwork::V6(n) => n.size(),
+    }
+}
+
+/// Walk a range lazily: a /16 is 65534 addresses we don't need to hold in memory.
+fn hosts(net: IpNetwork) -> impl Iterator<Item = IpAddr> {
+    let skip_ends = matches!(net, IpNetwork::V4(n) if n.prefix() < 31);
+    let count = usize::try_from(host_count(&net)).unwrap_or(usize::MAX);
+    net.iter().skip(skip_ends as usize).take(count)
+}
+
+/// Parse CIDR ranges and resolve names. Single addresses become /32 or /128 ranges.
+async fn parse_targets(targets: &[String]) -> Result<Vec<IpNetwork>, String> {
+    let mut nets = Vec::new();
+    for t in targets {
+        if let Ok(ip) = t.parse::<IpAddr>() {
+            nets.push(IpNetwork::from(ip));
+        } else if let Ok(net) = t.parse::<IpNetwork>() {
+            nets.push(net);
+        } else {
+            // lookup_host needs a port; it's ignored.
+            let mut addrs = lookup_host((t.as_str(), 0)).await.map_err(|e| format!("{}: {}", t, e))?;
+            if let Some(a) = addrs.next() {
+                nets.push(IpNetwork::from(a.ip()));
+            }
+        }
+    }
+    let total: u128 = nets.iter().map(host_count).sum();
+    if total > MAX_HOSTS {
+        return Err(format!("{} hosts is too many (limit {}); scan smaller ranges", total, MAX_HOSTS));
+    }
+    Ok(nets)
+}
+
+async fn tcp_probe(addr: SocketAddr, wait: Duration, retries: u32, grab: bool) -> Outcome {
+    let mut state = State::Filtered;
+    let mut banner = None;
+    for _ in 0..=retries {
+        match timeout(wait, TcpStream::connect(addr)).await {
+            Ok(Ok(stream)) => {
+                state = State::Open;
+                if grab {
+                    banner = grab_banner(stream, wait).await;
+                }
+                break;
+            }
+            // RST: the host answered, nothing listens there. No point retrying.
+            Ok(Err(e)) if e.kind() == ErrorKind::ConnectionRefused => {
+                state = State::Closed;
+                break;
+            }
+            // Timeout, or ICMP unreachable / no route: treat as filtered and try again.
+            Ok(Err(_)) | Err(_) => state = State::Filtered,
+        }
+    }
+    Outcome { addr, state, banner }
+}
+
+/// Many services talk first (SSH, SMTP, FTP). For the ones that don't, nudge HTTP ports.
+async fn grab_banner(mut stream: TcpStream, wait: Duration) -> Option<String> {
+    let mut buf = [0u8; 512];
+    // A silent service (read times out) is the case the nudge is for: count it as 0 bytes.
+    let mut n = timeout(wait, stream.read(&mut buf)).await.ok().and_then(Result::ok).unwrap_or(0);
+    if n == 0 && matches!(stream.peer_addr().ok()?.port(), 80 | 8000 | 8080 | 8443 | 443) {
+        stream.write_all(b"HEAD / HTTP/1.0\r\n\r\n").await.ok()?;
+        n = timeout(wait, stream.read(&mut buf)).await.ok()?.ok()?;
+    }
+    // First line only, printable characters only.
+    let text: String = String::from_utf8_lossy(&buf[..n])
+        .lines()
+        .next()?
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect();
+    (!text.is_empty()).then_some(text)
+}
+
+/// A payload the service will actually answer; an empty datagram gets ignored by most.
+fn udp_payload(port: u16) -> &'static [u8] {
+    match port {
+        // DNS: query for "." type NS
+        53 => &[0x12, 0x34, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01],
+        // NTP: version 4, client mode, rest zero
+        123 => &[
+            0x23, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ],
+        _ => b"\r\n",
+    }
+}
+
+async fn udp_probe(addr: SocketAddr, wait: Duration, retries: u32) -> Outcome {
+    let bind: SocketAddr = if addr.is_ipv6() { "[::]:0".parse().unwrap() } else { "0.0.0.0:0".parse().unwrap() };
+    let mut state = State::OpenFiltered;
+    for _ in 0..=retries {
+        let sock = match UdpSocket::bind(bind).await {
+            Ok(s) => s,
+            Err(_) => break,
+        };
+        // A *connected* UDP socket gets ICMP port-unreachable back as ConnectionRefused.
+        if sock.connect(addr).await.is_err() || sock.send(udp_payload(addr.port())).await.is_err() {
+            break;
+        }
+        let mut buf = [0u8; 512];
+        match timeout(wait, sock.recv(&mut buf)).await {
+            Ok(Ok(_)) => {
+                state = State::Open;
+                break;
+            }
+            Ok(Err(e)) if e.kind() == ErrorKind::ConnectionRefused => {
+                state = State::Closed;
+                break;
+            }
+            Ok(Err(_)) | Err(_) => state = State::OpenFiltered,
+        }
+    }
+    Outcome { addr, state, banner: None }
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    let ports = match parse_ports(&cli.ports) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    let nets = match parse_targets(&cli.targets).await {
+        Ok(n) => n,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let proto = if cli.udp { "udp" } else { "tcp" };
+    let host_total: u128 = nets.iter().map(host_count).sum();
+    println!("Scanning {} host(s) x {} {} port(s), {} in flight", host_total, ports.len(), proto, cli.concurrency);
+
+    // The semaphore caps how many probes run at once (and so how many sockets are open).
+    let limit = Arc::new(Semaphore::new(cli.concurrency.max(1)));
+    let wait = Duration::from_millis(cli.timeout_ms);
+    let mut tasks = JoinSet::new();
+
+    // Results are printed as they finish (not sorted): holding them all for a
+    // /16 x 65535 scan would be billions of entries.
+    let mut counts = [0usize; 4];
+    let mut report = |res: Result<Outcome, tokio::task::JoinError>| {
+        if let Ok(o) = res {
+            counts[o.state as usize] += 1;
+            if cli.all || o.state == State::Open {
+                println!("{:<40} {:<14} {}", format!("{}/{}", o.addr, proto), o.state.as_str(), o.banner.as_deref().unwrap_or(""));
+            }
+        }
+    };
+    println!("{:<40} {:<14} {}", "ADDRESS", "STATE", "BANNER");
+
+    for host in nets.iter().flat_map(|&net| hosts(net)) {
+        for &port in &ports {
+            let addr = SocketAddr::new(host, port);
+            // Finished tasks stay in the JoinSet until joined: collect them now,
+            // so the set never holds more than about `concurrency` entries.
+            while let Some(res) = tasks.try_join_next() {
+                report(res);
+            }
+            // acquire_owned() waits here until a ticket is free, so at most
+            // `concurrency` probes (and sockets) exist at any time.
+            let permit = limit.clone().acquire_owned().await.unwrap();
+            let (udp, retries, banner) = (cli.udp, cli.retries, cli.banner);
+            tasks.spawn(async move {
+                let out = if udp { udp_probe(addr, wait, retries).await } else { tcp_probe(addr, wait, retries, banner).await };
+                drop(permit); // give the ticket back
+                out
+            });
+        }
+    }
+
+    while let Some(res) = tasks.join_next().await {
+        report(res);
+    }
+    println!(
+        "\n{} open, {} closed, {} filtered, {} open|filtered",
+        counts[State::Open as usize],
+        counts[State::Closed as usize],
+        counts[State::Filtered as usize],
+        counts[State::OpenFiltered as usize]
+    );
+}
+```
+
+**Why this exact structure?**
+
+* `limit.clone().acquire_owned().await` *before* `tasks.spawn` — the loop itself pauses when all tickets are taken. Spawning first and acquiring inside the task would work too, but would create millions of waiting tasks for a big scan.
+* `timeout(wait, TcpStream::connect(addr))` — a filtered port never answers; without a timeout the OS would wait ~2 minutes per SYN.
+* `ConnectionRefused` → `Closed` and **no retry** — the host answered with RST, so the answer is definite. Only silence is retried (a SYN may have been lost).
+* `sock.connect(addr)` on the UDP socket — Linux only reports ICMP *port unreachable* to **connected** UDP sockets, as `ConnectionRefused` on the next `recv`.
+* `State::OpenFiltered` — a UDP service that ignores our payload looks exactly like a firewall that drops it. Good payloads (DNS, NTP) turn many of those into real `open` results.
+* `try_join_next()` inside the spawn loop — a finished task's result sits in the `JoinSet` until someone joins it. Draining while spawning keeps the set (and memory) small; results are printed in the order they finish.
+* `hosts(net)` is a lazy iterator — a range is never expanded into a `Vec`. Targets above a /16 worth of hosts are refused (`MAX_HOSTS`), since an IPv6 /64 would simply never end.
+* `grab_banner` — a read timeout means "the service waits for us to talk first", so it counts as 0 bytes and HTTP ports get the `HEAD` nudge.
+
+---
+
+## How to run
+
+```sh
+# your own SSH / web ports on a host
+cargo run --release -- 10.0.0.5 -p 22,80,443,8000-8100 --banner
+
+# a small subnet, TCP, everything shown
+cargo run --release -- 10.0.0.0/28 -p 22,3389 --all -t 500
+
+# DNS and NTP over UDP, two retries
+cargo run --release -- 10.0.0.53 --udp -p 53,123,161 -r 2
+```
+
+Output:
+
+```
+Scanning 1 host(s) x 104 tcp port(s), 256 in flight
+ADDRESS                                  STATE          BANNER
+10.0.0.5:22/tcp                          open           SSH-2.0-OpenSSH_9.6p1 Ubuntu-3ubuntu13
+10.0.0.5:80/tcp                          open           HTTP/1.1 301 Moved Permanently
+10.0.0.5:443/tcp                         open
+
+3 open, 100 closed, 1 filtered, 0 open|filtered
+```
+
+Try it against the echo server from `tokio.rs`: start it, then `cargo run -- 127.0.0.1 -p 8080 --all`.
+
+---
+
+## Common pitfalls & how to avoid them
+
+* **"Too many open files"** — every in-flight probe is a socket. Keep `--concurrency` below `ulimit -n` (often 1024).
+* **Everything shows `filtered`** — the timeout is too short for the network (try `-t 2000`), or a firewall really drops everything.
+* **UDP is slow and vague** — no reply is the normal case; expect `open|filtered` unless the service answers the payload. ICMP rate limiting on the target also hides `closed` ports.
+* **Scanning other people's networks** — don't. Connect scans show up clearly in logs and IDS alerts (see `NetworkInterface/scan_detect.rs`).
+
+---
@@ -0,0 +1,444 @@
+
+* To compare two versions of the sniffer (or of the echo server) you need **the same load twice**. "Open a few browser tabs" isn't reproducible; a traffic generator is.
+* This generator sends synthetic **TCP**, **UDP** or **ICMP** traffic to a target (usually `127.0.0.1` or a veth peer) with:
+
+  * a target **rate** (packets per second, shared by all flows),
+  * a **size distribution** — fixed, uniform `min-max`, or IMIX (the classic 7:4:1 mix of 64/576/1500-byte IP packets),
+  * a number of parallel **flows** (each its own socket, so its own source port → its own 5-tuple),
+  * optional **bursts** — N packets back to back, then a pause,
+  * a fixed **seed**, so the size sequence is identical between runs.
+
+* Every second it prints the achieved rate; at the end a summary (optionally JSON, to keep next to benchmark results).
+
+Good mental model: the rate limiter doesn't sleep *between packets* (timers are ~1 ms coarse). It wakes every millisecond, asks "how many packets should I have sent by now?", and sends the difference.
+
+---
+
+## Complete traffic generator
+
+`Cargo.toml`:
+
+```toml
+[package]
+name = "tokio_trafgen"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+tokio = { version = "1", features = ["full"] }
+clap = { version = "4", features = ["derive"] }
+socket2 = "0.5"
+serde = { version = "1", features = ["derive"] }
+serde_json = "1"
+```
+
+`src/main.rs`:
+
+```rust
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::time::{interval, sleep, MissedTickBehavior};
+
+#[derive(Clone, Copy, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Proto {
+    Tcp,
+    Udp,
+    Icmp,
+}
+
+#[derive(Parser)]
+struct Cli {
+    /// Where to send: 127.0.0.1:8080 (port ignored for ICMP)
+    target: SocketAddr,
+
+    #[arg(long, value_enum, default_value_t = Proto::Udp)]
+    proto: Proto,
+
+    /// Total packets (TCP: writes) per second across all flows; 0 = as fast as possible
+    #[arg(long, default_value_t = 1000)]
+    rate: u64,
+
+    /// Payload size: "512", "64-1400" (uniform) or "imix"
+    #[arg(long, default_value = "512")]
+    size: String,
+
+    /// Parallel flows (sockets)
+    #[arg(long, default_value_t = 1)]
+    flows: usize,
+
+    /// Send this many packets back to back, then pause for --burst-gap-ms
+    #[arg(long, default_value_t = 0)]
+    burst: u64,
+
+    #[arg(long, default_value_t = 100)]
+    burst_gap_ms: u64,
+
+    /// Seconds to run
+    #[arg(long, default_value_t = 10)]
+    duration: u64,
+
+    /// Seed for the size sequence
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+
+    /// Print the final report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+/// IP + transport header bytes in front of our payload, so IMIX sizes can be turned into payload sizes.
+/// TCP counts the 12-byte timestamp option, which Linux enables by default.
+/// ICMP: our payload already starts with the 8-byte Echo header, so only the IP header is added.
+fn header_overhead(proto: Proto, v6: bool) -> usize {
+    let ip = if v6 { 40 } else { 20 };
+    match proto {
+        Proto::Udp => ip + 8,
+        Proto::Tcp => ip + 32,
+        Proto::Icmp => ip,
+    }
+}
+
+#[derive(Clone)]
+enum SizeDist {
+    Fixed(usize),
+    Uniform(usize, usize),
+    /// 7 x 64, 4 x 576, 1 x 1500 (sizes of the whole IP packet in the original IMIX);
+    /// `overhead` is subtracted to get the payload size.
+    Imix { overhead: usize },
+}
+
+impl SizeDist {
+    fn parse(s: &str, overhead: usize) -> Result<Self, String> {
+        if s.eq_ignore_ascii_case("imix") {
+            // TCP over IPv6 needs 72 header bytes: the 64-byte IMIX packets can't exist there.
+            if overhead >= 64 {
+                return Err(format!("imix: {} header bytes don't fit in a 64-byte packet; use a range like 1-1428", overhead));
+            }
+            return Ok(SizeDist::Imix { overhead });
+        }
+        match s.split_once('-') {
+            Some((a, b)) => {
+                let (a, b): (usize, usize) = (a.parse().map_err(|_| s.to_string())?, b.parse().map_err(|_| s.to_string())?);
+                if a > b {
+                    return Err(format!("bad size range '{}'", s));
+                }
+                Ok(SizeDist::Uniform(a, b))
+            }
+            None => Ok(SizeDist::Fixed(s.parse().map_err(|_| format!("bad size '{}'", s))?)),
+        }
+    }
+
+    fn next(&self, rng: &mut XorShift) -> usize {
+        match *self {
+            SizeDist::Fixed(n) => n,
+            SizeDist::Uniform(a, b) => a + (rng.next() as usize) % (b - a + 1),
+            SizeDist::Imix { overhead } => {
+                let ip_len = match rng.next() % 12 {
+                    0..=6 => 64,
+                    7..=10 => 576,
+                    _ => 1500,
+                };
+                ip_len - overhead
+            }
+        }
+    }
+}
+
+/// Tiny deterministic PRNG: same seed, same sizes, no extra crate.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// Counters shared by all flows and the reporter.
+#[derive(Default)]
+struct Stats {
+    packets: AtomicU64,
+    bytes: AtomicU64,
+    errors: AtomicU64,
+    /// TCP only: bytes echoed back by the server
+    echoed: AtomicU64,
+}
+
+#[derive(Serialize)]
+struct Report {
+    proto: Proto,
+    target: SocketAddr,
+    flows: usize,
+    seconds: f64,
+    packets: u64,
+    bytes: u64,
+    errors: u64,
+    echoed_bytes: u64,
+    pps: f64,
+    mbps: f64,
+}
+
+/// One way to put bytes on the wire, whatever the protocol.
+enum Sender {
+    Udp(UdpSocket),
+    Tcp(tokio::net::tcp::OwnedWriteHalf),
+    Icmp(UdpSocket, u16),
+}
+
+impl Sender {
+    async fn open(proto: Proto, target: SocketAddr, stats: Arc<Stats>) -> std::io::Result<Sender> {
+        match proto {
+            Proto::Udp => {
+                let bind = if target.is_ipv6() { "[::]:0" } else { "0.0.0.0:0" };
+                let sock = UdpSocket::bind(bind).await?;
+                sock.connect(target).await?;
+                Ok(Sender::Udp(sock))
+            }
+            Proto::Tcp => {
+                let stream = TcpStream::connect(target).await?;
+                stream.set_nodelay(true)?;
+                let (mut rd, wr) = stream.into_split();
+                // Drain what the echo server sends back, or its buffers fill up and it stops reading.
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 64 * 1024];
+                    while let Ok(n) = rd.read(&mut buf).await {
+                        if n == 0 {
+                            break;
+                        }
+                        stats.echoed.fetch_add(n as u64, Ordering::Relaxed);
+                    }
+                });
+                Ok(Sender::Tcp(wr))
+            }
+            Proto::Icmp => {
+                // Unprivileged ping socket (see NetworkInterface/ping_traceroute.rs), driven by Tokio.
+                let (domain, proto) =
+                    if target.is_ipv6() { (Domain::IPV6, Protocol::ICMPV6) } else { (Domain::IPV4, Protocol::ICMPV4) };
+                let sock = Socket::new(domain, Type::DGRAM, Some(proto))?;
+                sock.set_nonblocking(true)?;
+                let sock = UdpSocket::from_std(sock.into())?;
+                sock.connect(SocketAddr::new(target.ip(), 0)).await?;
+                Ok(Sender::Icmp(sock, if target.is_ipv6() { 128 } else { 8 }))
+            }
+        }
+    }
+
+    async fn send(&mut self, payload: &mut [u8], seq: u16) -> std::io::Result<usize> {
+        match self {
+            Sender::Udp(s) => s.send(payload).await,
+            Sender::Tcp(s) => s.write_all(payload).await.map(|_| payload.len()),
+            Sender::Icmp(s, echo_type) => {
+                // Echo Request header in the first 8 bytes; ping sockets fill in id and checksum.
+                if payload.len() < 8 {
+                    return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "ICMP size must be >= 8"));
+                }
+                payload[..8].fill(0);
+                payload[0] = *echo_type;
+                payload[6..8].copy_from_slice(&seq.to_be_bytes());
+                s.send(payload).await
+            }
+        }
+    }
+}
+
+/// One flow: sends `rate` packets/s (0 = unlimited) until `deadline`.
+async fn run_flow(mut tx: Sender, cli: Arc<Cli>, sizes: SizeDist, rate: f64, seed: u64, stats: Arc<Stats>, deadline: Instant) {
+    let mut rng = XorShift(seed.max(1));
+    let mut buf = vec![0xabu8; 65_507];
+    let start = Instant::now();
+    let mut sent: u64 = 0;
+    let mut in_burst: u64 = 0;
+
+    let mut tick = interval(Duration::from_millis(1));
+    tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    while Instant::now() < deadline {
+        // How many packets are due by now? Unlimited: a batch of 64, then yield.
+        let due = if rate > 0.0 { (start.elapsed().as_secs_f64() * rate) as u64 } else { sent + 64 };
+        while sent < due {
+            let len = sizes.next(&mut rng).min(buf.len());
+            match tx.send(&mut buf[..len], sent as u16).await {
+                Ok(n) => {
+                    stats.packets.fetch_add(1, Ordering::Relaxed);
+                    stats.bytes.fetch_add(n as u64, Ordering::Relaxed);
+                }
+                Err(_) => {
+                    stats.errors.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            sent += 1;
+
+            in_burst += 1;
+            if cli.burst > 0 && in_burst >= cli.burst {
+                in_burst = 0;
+                sleep(Duration::from_millis(cli.burst_gap_ms)).await;
+                // Don't "catch up" the pause afterwards: the gap is part of the pattern.
+                sent = sent.max(if rate > 0.0 { (start.elapsed().as_secs_f64() * rate) as u64 } else { 0 });
+                break;
+            }
+        }
+        if rate > 0.0 {
+            tick.tick().await;
+        } else {
+            tokio::task::yield_now().await;
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Arc::new(Cli::parse());
+    let sizes = match SizeDist::parse(&cli.size, header_overhead(cli.proto, cli.target.is_ipv6())) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let stats = Arc::new(Stats::default());
+    let flows = cli.flows.max(1);
+    let per_flow_rate = cli.rate as f64 / flows as f64;
+    let start = Instant::now();
+    let deadline = start + Duration::from_secs(cli.duration);
+
+    let mut tasks = Vec::new();
+    for i in 0..flows {
+        let tx = match Sender::open(cli.proto, cli.target, stats.clone()).await {
+            Ok(tx) => tx,
+            Err(e) => {
+                eprintln!("flow {}: cannot open socket: {}", i, e);
+                std::process::exit(1);
+            }
+        };
+        // Each flow gets its own seed derived from --seed, so flows differ but runs repeat.
+        let seed = cli.seed.wrapping_mul(0x9E37_79B9_7F4A_7C15).wrapping_add(i as u64);
+        tasks.push(tokio::spawn(run_flow(tx, cli.clone(), sizes.clone(), per_flow_rate, seed, stats.clone(), deadline)));
+    }
+
+    // Reporter: once a second, the delta since the last report.
+    let reporter = {
+        let stats = stats.clone();
+        tokio::spawn(async move {
+            let mut tick = interval(Duration::from_secs(1));
+            tick.tick().await; // first tick fires immediately
+            let (mut last_p, mut last_b) = (0, 0);
+            loop {
+                tick.tick().await;
+                let p = stats.packets.load(Ordering::Relaxed);
+                let b = stats.bytes.load(Ordering::Relaxed);
+                println!(
+                    "{:>6.1}s  {:>10} pps  {:>9.2} Mbit/s  errors={}",
+                    start.elapsed().as_secs_f64(),
+                    p - last_p,
+                    (b - last_b) as f64 * 8.0 / 1e6,
+                    stats.errors.load(Ordering::Relaxed)
+                );
+                (last_p, last_b) = (p, b);
+            }
+        })
+    };
+
+    for t in tasks {
+        let _ = t.await;
+    }
+    reporter.abort();
+
+    let secs = start.elapsed().as_secs_f64();
+    let packets = stats.packets.load(Ordering::Relaxed);
+    let bytes = stats.bytes.load(Ordering::Relaxed);
+    let report = Report {
+        proto: cli.proto,
+        target: cli.target,
+        flows,
+        seconds: secs,
+        packets,
+        bytes,
+        errors: stats.errors.load(Ordering::Relaxed),
+        echoed_bytes: stats.echoed.load(Ordering::Relaxed),
+        pps: packets as f64 / secs,
+        mbps: bytes as f64 * 8.0 / 1e6 / secs,
+    };
+
+    if cli.json {
+        println!("{}", serde_json::to_string(&report).unwrap());
+    } else {
+        println!(
+            "\nsent {} packets / {} bytes in {:.2}s over {} flow(s): {:.0} pps, {:.2} Mbit/s, {} errors",
+            report.packets, report.bytes, report.seconds, report.flows, report.pps, report.mbps, report.errors
+        );
+        if let Proto::Tcp = cli.proto {
+            println!("echoed back: {} bytes", report.echoed_bytes);
+        }
+    }
+}
+```
+
+**Why this exact structure?**
+
+* `interval(1 ms)` + `due = elapsed * rate` — pacing by elapsed time instead of counting sleeps. If one tick is late, the next one sends a few more packets and the average rate stays right.
+* `MissedTickBehavior::Skip` — after a slow moment we don't want a storm of back-to-back ticks; the `due` calculation already catches up.
+* One socket per flow — a new socket gets a new ephemeral source port, so the sniffer and flow tables see `--flows` distinct 5-tuples.
+* `--size` is the **payload** (what `send` is given, and what `bytes` counts). IMIX is defined in IP packet sizes, so `header_overhead` is subtracted: over UDP/IPv4 the mix becomes 36/548/1472-byte payloads, averaging 354 − 28 ≈ 326 B. TCP over IPv6 has 72 bytes of headers, more than the smallest IMIX packet, so `--size imix` is rejected there.
+* `XorShift` with a fixed seed — uniform and IMIX sizes come out in the *same order* every run, which is what makes two runs comparable.
+* The TCP read half is drained in its own task — the echo server writes everything back; if nobody reads, both sides' buffers fill and the test measures a deadlock instead of throughput.
+* TCP "packets" are **writes**, not segments: with `set_nodelay(true)` small writes usually map to one segment each, but the kernel may still merge or split them.
+* `AtomicU64` with `Relaxed` — counters only; nothing else depends on their ordering.
+
+---
+
+## How to run
+
+```sh
+# 1) UDP at 20k pps, IMIX sizes, 8 flows, to a local sink (see the first pitfall)
+socat -u UDP-RECV:9000 /dev/null &
+cargo run --release -- 127.0.0.1:9000 --proto udp --rate 20000 --size imix --flows 8 --duration 10
+
+# 2) TCP against the echo server from tokio.rs (start it first)
+cargo run --release -- 127.0.0.1:8080 --proto tcp --rate 5000 --size 64-1400 --flows 4
+
+# 3) bursts: 500 packets back to back every 200 ms
+cargo run --release -- 10.200.0.2:9000 --rate 0 --burst 500 --burst-gap-ms 200
+
+# 4) ICMP echo (needs net.ipv4.ping_group_range to include your group)
+cargo run --release -- 127.0.0.1:0 --proto icmp --rate 1000 --size 56
+
+# keep a result per version
+cargo run --release -- 127.0.0.1:8080 --proto tcp --rate 0 --json >> bench-$(git rev-parse --short HEAD).jsonl
+```
+
+While it runs, point a sniffer at `lo` (`sudo cargo run -- --iface lo` in `NetworkInterface/`) and watch whether it keeps up.
+
+Output:
+
+```
+   1.0s       20001 pps     52.24 Mbit/s  errors=0
+   2.0s       19998 pps     52.19 Mbit/s  errors=0
+   ...
+  10.0s       20000 pps     52.23 Mbit/s  errors=0
+
+sent 200003 packets / 65266918 bytes in 10.00s over 8 flow(s): 20000 pps, 52.21 Mbit/s, 0 errors
+```
+
+Bytes and Mbit/s are payload: 65266918 / 200003 ≈ 326 B per datagram, the UDP/IPv4 IMIX average. On the wire (IP) that is 354 B per packet, ~56.7 Mbit/s.
+
+---
+
+## Common pitfalls & how to avoid them
+
+* **UDP errors to a closed port** — on loopback the kernel answers with ICMP port unreachable, and the *next* `send` on a connected socket fails with `ConnectionRefused`, so without a listener about every other send counts as an error. Run a sink that accepts any source port: `socat -u UDP-RECV:9000 /dev/null`. `nc -ul` connects to the first sender and refuses the other flows.
+* **Rate not reached** — `--rate 0` shows the ceiling. Build with `--release`; debug builds are several times slower.
+* **Sizes above 1472 bytes (UDP/IPv4)** — bigger datagrams are IP-fragmented. Fine on loopback (MTU 65536), but on Ethernet it changes what the sniffer sees.
+* **Comparing runs** — keep `--seed`, `--flows`, `--size` and `--duration` identical, otherwise the numbers aren't comparable.
+
+---